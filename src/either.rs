@@ -0,0 +1,2044 @@
+//! A value that is one of two possible types.
+
+use core::any::Any;
+use core::fmt::{self, Debug, Display};
+use core::ops::{ControlFlow, Deref, DerefMut, Neg};
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+pub mod routing;
+
+/// Holds either a `Left` or a `Right` value.
+///
+/// With the `serde` feature, values serialize as an externally tagged enum:
+/// `{"Left": x}` / `{"Right": y}` in self-describing formats, and a variant
+/// index followed by the payload in binary formats such as `bincode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+use Either::{Left, Right};
+
+impl<L, R> Either<L, R> {
+    /// Returns `true` if the value is a `Left`.
+    pub fn is_left(&self) -> bool {
+        matches!(self, Left(_))
+    }
+
+    /// Returns `true` if the value is a `Right`.
+    pub fn is_right(&self) -> bool {
+        matches!(self, Right(_))
+    }
+
+    /// Converts into an `Option` holding the left value, if any.
+    pub fn left(self) -> Option<L> {
+        match self {
+            Left(l) => Some(l),
+            Right(_) => None,
+        }
+    }
+
+    /// Converts into an `Option` holding the right value, if any.
+    pub fn right(self) -> Option<R> {
+        match self {
+            Left(_) => None,
+            Right(r) => Some(r),
+        }
+    }
+
+    /// Applies `f` to a `Left` value, leaving a `Right` untouched.
+    pub fn map_left<T, F: FnOnce(L) -> T>(self, f: F) -> Either<T, R> {
+        match self {
+            Left(l) => Left(f(l)),
+            Right(r) => Right(r),
+        }
+    }
+
+    /// Applies `f` to a `Right` value, leaving a `Left` untouched.
+    pub fn map_right<T, F: FnOnce(R) -> T>(self, f: F) -> Either<L, T> {
+        match self {
+            Left(l) => Left(l),
+            Right(r) => Right(f(r)),
+        }
+    }
+
+    /// Applies `f` to a `Left` or `g` to a `Right`, keeping the side.
+    pub fn map_either<T, U, F, G>(self, f: F, g: G) -> Either<T, U>
+    where
+        F: FnOnce(L) -> T,
+        G: FnOnce(R) -> U,
+    {
+        match self {
+            Left(l) => Left(f(l)),
+            Right(r) => Right(g(r)),
+        }
+    }
+
+    /// Swaps the sides: `Left` becomes `Right` and vice versa.
+    pub fn swap(self) -> Either<R, L> {
+        match self {
+            Left(l) => Right(l),
+            Right(r) => Left(r),
+        }
+    }
+
+    /// Converts into a `Result`, mapping a `Left` through `ok` and a `Right`
+    /// through `err`.
+    pub fn into_result_mapped<T, E>(
+        self,
+        ok: impl FnOnce(L) -> T,
+        err: impl FnOnce(R) -> E,
+    ) -> Result<T, E> {
+        match self {
+            Left(l) => Ok(ok(l)),
+            Right(r) => Err(err(r)),
+        }
+    }
+
+    /// Same as [`into_result_mapped`](Either::into_result_mapped), with the
+    /// closures as named type parameters.
+    ///
+    /// ```
+    /// use aurars::either::Either;
+    ///
+    /// let port: Either<&str, u16> = Either::Left("8080");
+    /// let parsed = port.map_into_result(|s| s.parse::<u16>().unwrap() + 1, |code| code);
+    /// assert_eq!(parsed, Ok(8081));
+    ///
+    /// let failed: Either<&str, u16> = Either::Right(404);
+    /// let result = failed.map_into_result(str::len, |code| format!("HTTP {code}"));
+    /// assert_eq!(result, Err("HTTP 404".to_string()));
+    /// ```
+    pub fn map_into_result<T, E, F, G>(self, ok: F, err: G) -> Result<T, E>
+    where
+        F: FnOnce(L) -> T,
+        G: FnOnce(R) -> E,
+    {
+        self.into_result_mapped(ok, err)
+    }
+
+    /// Returns the left value, or `L::default()` for a `Right`.
+    pub fn left_or_default(self) -> L
+    where
+        L: Default,
+    {
+        self.unwrap_left_or_default()
+    }
+
+    /// Returns the right value, or `R::default()` for a `Left`.
+    pub fn right_or_default(self) -> R
+    where
+        R: Default,
+    {
+        self.unwrap_right_or_default()
+    }
+
+    /// Like [`map_either`](Either::map_either), but borrows the contents
+    /// instead of consuming `self`.
+    pub fn map_either_ref<T, U, F, G>(&self, f: F, g: G) -> Either<T, U>
+    where
+        F: Fn(&L) -> T,
+        G: Fn(&R) -> U,
+    {
+        match self {
+            Left(l) => Left(f(l)),
+            Right(r) => Right(g(r)),
+        }
+    }
+
+    /// Returns the left value, or the right value as the error.
+    pub fn try_unwrap_left(self) -> Result<L, R> {
+        self.into()
+    }
+
+    /// Returns the right value, or the left value as the error.
+    pub fn try_unwrap_right(self) -> Result<R, L> {
+        self.swap().into()
+    }
+
+    /// Returns the right value, converting a `Left` into an `R` with `f`.
+    pub fn fold_left_into_right(self, f: impl FnOnce(L) -> R) -> R {
+        match self {
+            Left(l) => f(l),
+            Right(r) => r,
+        }
+    }
+
+    /// Borrows the contents with the sides swapped.
+    pub fn swap_ref(&self) -> Either<&R, &L> {
+        match self {
+            Left(l) => Right(l),
+            Right(r) => Left(r),
+        }
+    }
+
+    /// Returns a clone of the left value, or builds one from a borrow of the
+    /// right value with `f`.
+    pub fn left_or_with_right_ref(&self, f: impl FnOnce(&R) -> L) -> L
+    where
+        L: Clone,
+    {
+        match self {
+            Left(l) => l.clone(),
+            Right(r) => f(r),
+        }
+    }
+
+    /// Replaces a `Left` with `Right(value)`, then returns a mutable
+    /// reference to the right value.
+    pub fn get_or_insert_right(&mut self, value: R) -> &mut R {
+        if let Left(_) = self {
+            *self = Right(value);
+        }
+        match self {
+            Right(r) => r,
+            Left(_) => unreachable!(),
+        }
+    }
+
+    /// Pushes a `Left` onto `lefts` or a `Right` onto `rights`.
+    pub fn push_into(self, lefts: &mut Vec<L>, rights: &mut Vec<R>) {
+        match self {
+            Left(l) => lefts.push(l),
+            Right(r) => rights.push(r),
+        }
+    }
+
+    /// Converts each side into another type through its `From` impl.
+    pub fn convert<L2: From<L>, R2: From<R>>(self) -> Either<L2, R2> {
+        self.map_either(L2::from, R2::from)
+    }
+
+    /// Picks `table[0]` for a `Left` and `table[1]` for a `Right`.
+    pub fn select<'a, T>(&self, table: &'a [T; 2]) -> &'a T {
+        match self {
+            Left(_) => &table[0],
+            Right(_) => &table[1],
+        }
+    }
+
+    /// Borrows the contents, keeping the side.
+    pub fn as_ref(&self) -> Either<&L, &R> {
+        match self {
+            Left(l) => Left(l),
+            Right(r) => Right(r),
+        }
+    }
+
+    /// Mutably borrows the contents, keeping the side.
+    pub fn as_mut(&mut self) -> Either<&mut L, &mut R> {
+        match self {
+            Left(l) => Left(l),
+            Right(r) => Right(r),
+        }
+    }
+
+    /// Collapses into a single value by applying `f` to a `Left` or `g` to a
+    /// `Right`.
+    ///
+    /// ```
+    /// use aurars::either::Either;
+    ///
+    /// let e = Either::<i32, &str>::Right("hi");
+    /// assert_eq!(e.either(|n| n.to_string(), |s| s.to_string()), "hi");
+    /// ```
+    pub fn either<T, F, G>(self, f: F, g: G) -> T
+    where
+        F: FnOnce(L) -> T,
+        G: FnOnce(R) -> T,
+    {
+        match self {
+            Left(l) => f(l),
+            Right(r) => g(r),
+        }
+    }
+
+    /// Chains a `Left` into `f`, which may move it to either side. A `Right`
+    /// passes through unchanged.
+    ///
+    /// ```
+    /// use aurars::either::Either::{self, Left, Right};
+    ///
+    /// let halve = |n: i32| if n % 2 == 0 { Left(n / 2) } else { Right("odd") };
+    /// assert_eq!(Left(8).left_and_then(halve).left_and_then(halve), Left(2));
+    /// assert_eq!(Left(6).left_and_then(halve).left_and_then(halve), Right("odd"));
+    /// // Already on the right side: `halve` is never called.
+    /// assert_eq!(Either::<i32, _>::Right("nope").left_and_then(halve), Right("nope"));
+    /// ```
+    pub fn left_and_then<U, F: FnOnce(L) -> Either<U, R>>(self, f: F) -> Either<U, R> {
+        match self {
+            Left(l) => f(l),
+            Right(r) => Right(r),
+        }
+    }
+
+    /// Chains a `Right` into `f`, which may move it to either side. A `Left`
+    /// passes through unchanged.
+    ///
+    /// ```
+    /// use aurars::either::Either::{self, Left, Right};
+    ///
+    /// let parse = |s: &str| s.parse::<i32>().map_or(Left("not a number"), Right);
+    /// assert_eq!(Either::<&str, &str>::Right("42").right_and_then(parse), Right(42));
+    /// // Already on the left side: `parse` is never called.
+    /// assert_eq!(Either::<&str, &str>::Left("skip").right_and_then(parse), Left("skip"));
+    /// ```
+    pub fn right_and_then<U, F: FnOnce(R) -> Either<L, U>>(self, f: F) -> Either<L, U> {
+        match self {
+            Left(l) => Left(l),
+            Right(r) => f(r),
+        }
+    }
+
+    /// Returns the left value, or `default` for a `Right`.
+    ///
+    /// ```
+    /// use aurars::either::Either::{self, Left, Right};
+    ///
+    /// assert_eq!(Either::<i32, &str>::Left(3).unwrap_left_or(0), 3);
+    /// assert_eq!(Either::<i32, &str>::Right("x").unwrap_left_or(0), 0);
+    /// ```
+    pub fn unwrap_left_or(self, default: L) -> L {
+        match self {
+            Left(l) => l,
+            Right(_) => default,
+        }
+    }
+
+    /// Returns the left value, or computes one from the right value with `f`.
+    ///
+    /// ```
+    /// use aurars::either::Either::{self, Left, Right};
+    ///
+    /// assert_eq!(Either::<usize, &str>::Left(3).unwrap_left_or_else(str::len), 3);
+    /// assert_eq!(Either::<usize, &str>::Right("four").unwrap_left_or_else(str::len), 4);
+    /// ```
+    pub fn unwrap_left_or_else<F: FnOnce(R) -> L>(self, f: F) -> L {
+        match self {
+            Left(l) => l,
+            Right(r) => f(r),
+        }
+    }
+
+    /// Returns the left value, or `L::default()` for a `Right`.
+    ///
+    /// ```
+    /// use aurars::either::Either::{self, Left, Right};
+    ///
+    /// assert_eq!(Either::<String, i32>::Left("aura".into()).unwrap_left_or_default(), "aura");
+    /// assert_eq!(Either::<String, i32>::Right(1).unwrap_left_or_default(), "");
+    /// ```
+    pub fn unwrap_left_or_default(self) -> L
+    where
+        L: Default,
+    {
+        self.unwrap_left_or_else(|_| L::default())
+    }
+
+    /// Returns the right value, or `default` for a `Left`.
+    ///
+    /// ```
+    /// use aurars::either::Either::{self, Left, Right};
+    ///
+    /// assert_eq!(Either::<&str, i32>::Right(3).unwrap_right_or(0), 3);
+    /// assert_eq!(Either::<&str, i32>::Left("x").unwrap_right_or(0), 0);
+    /// ```
+    pub fn unwrap_right_or(self, default: R) -> R {
+        match self {
+            Left(_) => default,
+            Right(r) => r,
+        }
+    }
+
+    /// Returns the right value, or computes one from the left value with `f`.
+    ///
+    /// ```
+    /// use aurars::either::Either::{self, Left, Right};
+    ///
+    /// assert_eq!(Either::<&str, usize>::Right(3).unwrap_right_or_else(str::len), 3);
+    /// assert_eq!(Either::<&str, usize>::Left("four").unwrap_right_or_else(str::len), 4);
+    /// ```
+    pub fn unwrap_right_or_else<F: FnOnce(L) -> R>(self, f: F) -> R {
+        match self {
+            Left(l) => f(l),
+            Right(r) => r,
+        }
+    }
+
+    /// Returns the right value, or `R::default()` for a `Left`.
+    ///
+    /// ```
+    /// use aurars::either::Either::{self, Left, Right};
+    ///
+    /// assert_eq!(Either::<i32, Vec<u8>>::Right(vec![1]).unwrap_right_or_default(), vec![1]);
+    /// assert_eq!(Either::<i32, Vec<u8>>::Left(1).unwrap_right_or_default(), Vec::<u8>::new());
+    /// ```
+    pub fn unwrap_right_or_default(self) -> R
+    where
+        R: Default,
+    {
+        self.unwrap_right_or_else(|_| R::default())
+    }
+
+    /// Pairs a `Right` with `ctx`, leaving a `Left` untouched.
+    pub fn context<C>(self, ctx: C) -> Either<L, (C, R)> {
+        self.map_right(|r| (ctx, r))
+    }
+
+    /// Pairs a `Right` with the context `f` builds, leaving a `Left`
+    /// untouched. `f` only runs for a `Right`.
+    pub fn with_context<C, F: FnOnce() -> C>(self, f: F) -> Either<L, (C, R)> {
+        self.map_right(|r| (f(), r))
+    }
+
+    /// Takes the value out of a `Left`, leaving `Left(L::default())` in its
+    /// place. Returns `None` and leaves `self` unchanged for a `Right`.
+    pub fn take_left(&mut self) -> Option<L>
+    where
+        L: Default,
+    {
+        match self {
+            Left(l) => Some(core::mem::take(l)),
+            Right(_) => None,
+        }
+    }
+
+    /// Takes the value out of a `Right`, leaving `Right(R::default())` in
+    /// its place. Returns `None` and leaves `self` unchanged for a `Left`.
+    pub fn take_right(&mut self) -> Option<R>
+    where
+        R: Default,
+    {
+        match self {
+            Left(_) => None,
+            Right(r) => Some(core::mem::take(r)),
+        }
+    }
+
+    /// Combines two `Left`s with `f`. Otherwise returns the first `Right`,
+    /// checking `self` before `other`.
+    pub fn zip_with<L2, T, F: FnOnce(L, L2) -> T>(
+        self,
+        other: Either<L2, R>,
+        f: F,
+    ) -> Either<T, R> {
+        match (self, other) {
+            (Left(a), Left(b)) => Left(f(a, b)),
+            (Right(r), _) | (Left(_), Right(r)) => Right(r),
+        }
+    }
+
+    /// Pairs `self` with `other` when both are on the same side, or returns
+    /// `None` if they differ.
+    pub fn zip<L2, R2>(self, other: Either<L2, R2>) -> Option<Either<(L, L2), (R, R2)>> {
+        match (self, other) {
+            (Left(a), Left(b)) => Some(Left((a, b))),
+            (Right(a), Right(b)) => Some(Right((a, b))),
+            _ => None,
+        }
+    }
+
+    /// Replaces `self` with `f` applied to its current value, which may move
+    /// it to the other side.
+    ///
+    /// `L: Default` is needed to leave a placeholder `Left(L::default())` in
+    /// `self` while `f` owns the old value. If `f` panics, the placeholder
+    /// is what remains.
+    pub fn replace_with(&mut self, f: impl FnOnce(Either<L, R>) -> Either<L, R>)
+    where
+        L: Default,
+    {
+        let old = core::mem::replace(self, Left(L::default()));
+        *self = f(old);
+    }
+
+    /// Turns a `Left` into `Ok` and maps a `Right` into the error with `f`.
+    ///
+    /// ```
+    /// use aurars::either::Either;
+    ///
+    /// let parsed: Either<i32, &str> = Either::Right("oops");
+    /// assert_eq!(parsed.left_or(|r| format!("bad input: {r}")), Err("bad input: oops".to_string()));
+    /// assert_eq!(Either::<i32, &str>::Left(3).left_or(str::len), Ok(3));
+    /// ```
+    pub fn left_or<F: FnOnce(R) -> E, E>(self, f: F) -> Result<L, E> {
+        self.try_unwrap_left().map_err(f)
+    }
+
+    /// Turns a `Right` into `Ok` and maps a `Left` into the error with `f`,
+    /// so the `Left` side becomes the error.
+    ///
+    /// ```
+    /// use aurars::either::Either;
+    ///
+    /// let cached: Either<u32, String> = Either::Left(404);
+    /// assert_eq!(cached.right_or(|code| format!("status {code}")), Err("status 404".to_string()));
+    /// assert_eq!(Either::<u32, &str>::Right("body").right_or(|c| c + 1), Ok("body"));
+    /// ```
+    pub fn right_or<F: FnOnce(L) -> E, E>(self, f: F) -> Result<R, E> {
+        self.try_unwrap_right().map_err(f)
+    }
+
+    /// Returns the `Left` value if `pred` holds for it, otherwise `None`.
+    pub fn filter_left<P: FnOnce(&L) -> bool>(self, pred: P) -> Option<L> {
+        match self {
+            Left(l) if pred(&l) => Some(l),
+            _ => None,
+        }
+    }
+
+    /// Returns the `Right` value if `pred` holds for it, otherwise `None`.
+    pub fn filter_right<P: FnOnce(&R) -> bool>(self, pred: P) -> Option<R> {
+        match self {
+            Right(r) if pred(&r) => Some(r),
+            _ => None,
+        }
+    }
+
+    /// Builds a `Left` from `Some`, or a `Right` holding `right` from
+    /// `None`.
+    pub fn ok_or_left(option: Option<L>, right: R) -> Either<L, R> {
+        Either::ok_or_else_left(option, || right)
+    }
+
+    /// Like [`ok_or_left`](Either::ok_or_left), but only builds the `Right`
+    /// with `f` for `None`.
+    pub fn ok_or_else_left<F: FnOnce() -> R>(option: Option<L>, f: F) -> Either<L, R> {
+        match option {
+            Some(l) => Left(l),
+            None => Right(f()),
+        }
+    }
+
+    /// Builds a `Right` from `Some`, or a `Left` holding `left` from `None`.
+    pub fn ok_or_right(option: Option<R>, left: L) -> Either<L, R> {
+        Either::ok_or_else_right(option, || left)
+    }
+
+    /// Like [`ok_or_right`](Either::ok_or_right), but only builds the
+    /// `Left` with `f` for `None`.
+    pub fn ok_or_else_right<F: FnOnce() -> L>(option: Option<R>, f: F) -> Either<L, R> {
+        match option {
+            Some(r) => Right(r),
+            None => Left(f()),
+        }
+    }
+
+    /// Borrows the dereferenced value on whichever side, like
+    /// [`Option::as_deref`].
+    pub fn as_deref(&self) -> Either<&L::Target, &R::Target>
+    where
+        L: Deref,
+        R: Deref,
+    {
+        self.as_ref().map_either(Deref::deref, Deref::deref)
+    }
+
+    /// Mutably borrows the dereferenced value on whichever side, like
+    /// [`Option::as_deref_mut`].
+    pub fn as_deref_mut(&mut self) -> Either<&mut L::Target, &mut R::Target>
+    where
+        L: DerefMut,
+        R: DerefMut,
+    {
+        self.as_mut()
+            .map_either(DerefMut::deref_mut, DerefMut::deref_mut)
+    }
+
+    /// Returns the `Left` value, or panics with the message `f` builds from
+    /// the `Right`. `f` only runs when panicking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a `Right`.
+    #[track_caller]
+    pub fn unwrap_left_with<F: FnOnce(&R) -> String>(self, f: F) -> L {
+        match self {
+            Left(l) => l,
+            Right(r) => panic!("{}", f(&r)),
+        }
+    }
+
+    /// Returns the `Right` value, or panics with the message `f` builds from
+    /// the `Left`. `f` only runs when panicking.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is a `Left`.
+    #[track_caller]
+    pub fn unwrap_right_with<F: FnOnce(&L) -> String>(self, f: F) -> R {
+        match self {
+            Left(l) => panic!("{}", f(&l)),
+            Right(r) => r,
+        }
+    }
+}
+
+impl<L: Debug, R: Debug> Either<L, R> {
+    /// Returns the side's name alongside the `Debug` form of its value.
+    pub fn debug_tag(&self) -> (&'static str, String) {
+        match self {
+            Left(l) => ("Left", format!("{:?}", l)),
+            Right(r) => ("Right", format!("{:?}", r)),
+        }
+    }
+}
+
+impl<L: Any, R: Any> Either<L, R> {
+    /// Yields the active value once, type-erased as `&dyn Any`.
+    pub fn iter_any(&self) -> impl Iterator<Item = &dyn Any> {
+        let value: &dyn Any = match self {
+            Left(l) => l,
+            Right(r) => r,
+        };
+        core::iter::once(value)
+    }
+}
+
+impl<A, R> Either<Vec<A>, R> {
+    /// Maps each element of a left vector with `f`, leaving a `Right`
+    /// untouched.
+    ///
+    /// Since `f` keeps the element type, collecting the mapped elements may
+    /// reuse the original allocation, though this is not guaranteed.
+    pub fn map_left_each(self, f: impl FnMut(A) -> A) -> Either<Vec<A>, R> {
+        self.map_left(|items| items.into_iter().map(f).collect())
+    }
+
+    /// Pairs every element of a left vector with every element of `other`'s
+    /// left vector, in row-major order. Otherwise returns the first
+    /// `Right`, as [`zip_with`](Either::zip_with) does.
+    pub fn cartesian<B>(self, other: Either<Vec<B>, R>) -> Either<Vec<(A, B)>, R>
+    where
+        A: Clone,
+        B: Clone,
+    {
+        self.zip_with(other, |xs, ys| {
+            xs.iter()
+                .flat_map(|x| ys.iter().map(move |y| (x.clone(), y.clone())))
+                .collect()
+        })
+    }
+}
+
+impl<T> Either<T, T> {
+    /// Routes `value` to `Left` if `pred` holds for it, otherwise to
+    /// `Right`.
+    pub fn split<P: FnOnce(&T) -> bool>(value: T, pred: P) -> Self {
+        if pred(&value) {
+            Left(value)
+        } else {
+            Right(value)
+        }
+    }
+
+    /// Yields a borrow of the contained value, whichever side it is on.
+    pub fn iter(&self) -> core::iter::Once<&T> {
+        match self {
+            Left(v) | Right(v) => core::iter::once(v),
+        }
+    }
+
+    /// Yields a mutable borrow of the contained value, whichever side it is
+    /// on.
+    pub fn iter_mut(&mut self) -> core::iter::Once<&mut T> {
+        match self {
+            Left(v) | Right(v) => core::iter::once(v),
+        }
+    }
+
+    /// Moves the value to the other side in place, turning `Left(v)` into
+    /// `Right(v)` and back.
+    pub fn swap_in_place(&mut self) {
+        // SAFETY: the value is read out and the swapped either written back
+        // with nothing in between that can panic, so `self` is never
+        // observed or dropped while moved from.
+        unsafe {
+            let swapped = core::ptr::read(self).swap();
+            core::ptr::write(self, swapped);
+        }
+    }
+
+    /// Applies `f` to the value on whichever side, keeping the side.
+    ///
+    /// ```
+    /// use aurars::either::Either::{self, Left, Right};
+    ///
+    /// let double = |n: i32| n * 2;
+    /// assert_eq!(Either::<i32, i32>::Left(3).map(double), Left(6));
+    /// assert_eq!(Either::<i32, i32>::Right(4).map(double), Right(8));
+    /// ```
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> Either<U, U> {
+        match self {
+            Left(v) => Left(f(v)),
+            Right(v) => Right(f(v)),
+        }
+    }
+}
+
+/// Yields the contained value once, whichever side it is on.
+impl<T> IntoIterator for Either<T, T> {
+    type Item = T;
+    type IntoIter = core::iter::Once<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        match self {
+            Left(v) | Right(v) => core::iter::once(v),
+        }
+    }
+}
+
+impl<L: IntoIterator, R: IntoIterator<Item = L::Item>> Either<L, R> {
+    /// Iterates over the items of whichever side is present.
+    ///
+    /// ```
+    /// use aurars::either::Either;
+    ///
+    /// let items: Either<Vec<i32>, std::ops::Range<i32>> = Either::Right(0..3);
+    /// assert_eq!(items.into_either_iter().sum::<i32>(), 3);
+    /// ```
+    pub fn into_either_iter(self) -> EitherIter<L::IntoIter, R::IntoIter> {
+        EitherIter(self.map_either(L::into_iter, R::into_iter))
+    }
+}
+
+/// Iterator over the active side of an `Either` of iterators, created by
+/// [`Either::into_either_iter`].
+#[derive(Debug, Clone)]
+pub struct EitherIter<L, R>(Either<L, R>);
+
+impl<L: Iterator, R: Iterator<Item = L::Item>> Iterator for EitherIter<L, R> {
+    type Item = L::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.0 {
+            Left(l) => l.next(),
+            Right(r) => r.next(),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match &self.0 {
+            Left(l) => l.size_hint(),
+            Right(r) => r.size_hint(),
+        }
+    }
+}
+
+/// Borrows the active side as `&U`, so an `Either<String, &str>` can go
+/// wherever an `impl AsRef<str>` is expected.
+///
+/// Method syntax picks the inherent [`Either::as_ref`]; call
+/// `AsRef::<U>::as_ref(&e)` to reach this impl directly.
+impl<U: ?Sized, L: AsRef<U>, R: AsRef<U>> AsRef<U> for Either<L, R> {
+    fn as_ref(&self) -> &U {
+        match self {
+            Left(l) => l.as_ref(),
+            Right(r) => r.as_ref(),
+        }
+    }
+}
+
+/// Defaults to `Left(L::default())`.
+impl<L: Default, R> Default for Either<L, R> {
+    fn default() -> Self {
+        Left(L::default())
+    }
+}
+
+/// Negates the value on whichever side, keeping the side.
+impl<L: Neg, R: Neg> Neg for Either<L, R> {
+    type Output = Either<L::Output, R::Output>;
+
+    fn neg(self) -> Self::Output {
+        self.map_either(Neg::neg, Neg::neg)
+    }
+}
+
+/// Formats whichever side is active, with no `Left`/`Right` prefix.
+impl<L: Display, R: Display> Display for Either<L, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Left(l) => l.fmt(f),
+            Right(r) => r.fmt(f),
+        }
+    }
+}
+
+/// An `Either` of two error types is itself an error, forwarding `source`
+/// to the active side.
+#[cfg(feature = "std")]
+impl<L: std::error::Error, R: std::error::Error> std::error::Error for Either<L, R> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Left(l) => l.source(),
+            Right(r) => r.source(),
+        }
+    }
+}
+
+impl<L, R> Either<Option<L>, Option<R>> {
+    /// Unwraps the option on the active side, giving `None` if it is empty.
+    pub fn flatten_options(self) -> Option<Either<L, R>> {
+        match self {
+            Left(l) => l.map(Left),
+            Right(r) => r.map(Right),
+        }
+    }
+
+    /// Turns an `Either` of options into an option of `Either`, like
+    /// [`Result::transpose`]. Same as
+    /// [`flatten_options`](Either::flatten_options).
+    pub fn transpose(self) -> Option<Either<L, R>> {
+        self.flatten_options()
+    }
+}
+
+impl<L, R> Either<Either<L, R>, R> {
+    /// Removes one level of nesting from the left side.
+    pub fn flatten(self) -> Either<L, R> {
+        match self {
+            Left(inner) => inner,
+            Right(r) => Right(r),
+        }
+    }
+}
+
+impl<L, R> Either<L, Either<L, R>> {
+    /// Removes one level of nesting from the right side, mirroring
+    /// [`flatten`](Either::flatten).
+    pub fn flatten_right(self) -> Either<L, R> {
+        match self {
+            Left(l) => Left(l),
+            Right(inner) => inner,
+        }
+    }
+}
+
+impl<L, R> Either<&L, &R> {
+    /// Clones the borrowed value on whichever side, like [`Option::cloned`].
+    pub fn cloned(self) -> Either<L, R>
+    where
+        L: Clone,
+        R: Clone,
+    {
+        self.map_either(L::clone, R::clone)
+    }
+
+    /// Copies the borrowed value on whichever side, like [`Option::copied`].
+    pub fn copied(self) -> Either<L, R>
+    where
+        L: Copy,
+        R: Copy,
+    {
+        self.map_either(|l| *l, |r| *r)
+    }
+}
+
+impl<T, A, B> Either<(T, A), (T, B)> {
+    /// Pulls the first element, shared by both sides, out of the pair.
+    pub fn factor_first(self) -> (T, Either<A, B>) {
+        match self {
+            Left((t, a)) => (t, Left(a)),
+            Right((t, b)) => (t, Right(b)),
+        }
+    }
+}
+
+impl<T, A, B> Either<(A, T), (B, T)> {
+    /// Pulls the second element, shared by both sides, out of the pair.
+    pub fn factor_second(self) -> (Either<A, B>, T) {
+        match self {
+            Left((a, t)) => (Left(a), t),
+            Right((b, t)) => (Right(b), t),
+        }
+    }
+}
+
+#[cfg(feature = "anyhow")]
+impl<L, R> Either<L, R>
+where
+    R: std::error::Error + Send + Sync + 'static,
+{
+    /// Converts into an `anyhow::Result`, turning a `Right` error into an
+    /// `anyhow::Error`.
+    pub fn into_anyhow(self) -> anyhow::Result<L> {
+        match self {
+            Left(l) => Ok(l),
+            Right(r) => Err(r.into()),
+        }
+    }
+}
+
+impl<L, R> From<Result<L, R>> for Either<L, R> {
+    fn from(result: Result<L, R>) -> Self {
+        match result {
+            Ok(l) => Left(l),
+            Err(r) => Right(r),
+        }
+    }
+}
+
+impl<L, R> From<Either<L, R>> for Result<L, R> {
+    fn from(either: Either<L, R>) -> Self {
+        match either {
+            Left(l) => Ok(l),
+            Right(r) => Err(r),
+        }
+    }
+}
+
+/// `Continue` maps to `Left` and `Break` maps to `Right`.
+impl<B, C> From<ControlFlow<B, C>> for Either<C, B> {
+    fn from(flow: ControlFlow<B, C>) -> Self {
+        match flow {
+            ControlFlow::Continue(c) => Left(c),
+            ControlFlow::Break(b) => Right(b),
+        }
+    }
+}
+
+/// `Left` maps to `Continue` and `Right` maps to `Break`.
+impl<B, C> From<Either<C, B>> for ControlFlow<B, C> {
+    fn from(either: Either<C, B>) -> Self {
+        match either {
+            Left(c) => ControlFlow::Continue(c),
+            Right(b) => ControlFlow::Break(b),
+        }
+    }
+}
+
+/// Splits an iterator of `Either`s into a collection of its lefts and a
+/// collection of its rights, preserving order within each.
+pub fn partition_either<I, A, B, L, R>(iter: I) -> (A, B)
+where
+    I: IntoIterator<Item = Either<L, R>>,
+    A: Default + Extend<L>,
+    B: Default + Extend<R>,
+{
+    let mut lefts = A::default();
+    let mut rights = B::default();
+    for either in iter {
+        match either {
+            Left(l) => lefts.extend(Some(l)),
+            Right(r) => rights.extend(Some(r)),
+        }
+    }
+    (lefts, rights)
+}
+
+/// Splits an iterator of `Either`s into its lefts and rights, preallocating
+/// each vector with the given capacity.
+pub fn partition_either_with_capacity<L, R, I>(
+    iter: I,
+    left_cap: usize,
+    right_cap: usize,
+) -> (Vec<L>, Vec<R>)
+where
+    I: IntoIterator<Item = Either<L, R>>,
+{
+    let mut lefts = Vec::with_capacity(left_cap);
+    let mut rights = Vec::with_capacity(right_cap);
+    for either in iter {
+        match either {
+            Left(l) => lefts.push(l),
+            Right(r) => rights.push(r),
+        }
+    }
+    (lefts, rights)
+}
+
+/// Values paired with their positions in the sequence they came from.
+pub type Indexed<T> = Vec<(usize, T)>;
+
+/// Splits `iter` into its `Left` and `Right` values like
+/// [`partition_either`], pairing each with its position in `iter` so the
+/// two sides can be merged back in order later.
+pub fn partition_indexed<L, R, I>(iter: I) -> (Indexed<L>, Indexed<R>)
+where
+    I: IntoIterator<Item = Either<L, R>>,
+{
+    let mut lefts = Vec::new();
+    let mut rights = Vec::new();
+    for (i, either) in iter.into_iter().enumerate() {
+        match either {
+            Left(l) => lefts.push((i, l)),
+            Right(r) => rights.push((i, r)),
+        }
+    }
+    (lefts, rights)
+}
+
+/// Same as [`partition_indexed`], named alongside [`partition_either`].
+pub fn partition_either_indexed<I, L, R>(iter: I) -> (Indexed<L>, Indexed<R>)
+where
+    I: IntoIterator<Item = Either<L, R>>,
+{
+    partition_indexed(iter)
+}
+
+/// Collects `iter` into a vector on the `Left` side.
+pub fn collect_left<L, R, I: IntoIterator<Item = L>>(iter: I) -> Either<Vec<L>, R> {
+    Left(iter.into_iter().collect())
+}
+
+/// Collects `iter` into a vector on the `Right` side.
+pub fn collect_right<L, R, I: IntoIterator<Item = R>>(iter: I) -> Either<L, Vec<R>> {
+    Right(iter.into_iter().collect())
+}
+
+/// Borrows each element of `slice` as a `Left` when `pred` holds and as a
+/// `Right` otherwise.
+pub fn classify_slice<T>(slice: &[T], pred: impl Fn(&T) -> bool) -> Vec<Either<&T, &T>> {
+    slice
+        .iter()
+        .map(|item| if pred(item) { Left(item) } else { Right(item) })
+        .collect()
+}
+
+/// Lifts `f` to work on eithers: it maps a `Left` and passes a `Right`
+/// through, like [`Either::map_left`].
+pub fn lift<A, B, R>(f: impl Fn(A) -> B) -> impl Fn(Either<A, R>) -> Either<B, R> {
+    move |either| either.map_left(&f)
+}
+
+/// Clones every `Left` in `slice` into a vector, or clones the first
+/// `Right` if there is one.
+pub fn zip_all<L: Clone, R: Clone>(slice: &[Either<L, R>]) -> Either<Vec<L>, R> {
+    let mut lefts = Vec::with_capacity(slice.len());
+    for item in slice {
+        match item {
+            Left(l) => lefts.push(l.clone()),
+            Right(r) => return Right(r.clone()),
+        }
+    }
+    Left(lefts)
+}
+
+/// Returns every `Left` if `v` holds no `Right`, otherwise every `Right`,
+/// dropping the lefts.
+///
+/// Unlike [`partition_either`], only one side is kept. An empty `v` gives
+/// an empty `Left`.
+pub fn sequence_or_rights<L, R>(v: Vec<Either<L, R>>) -> Either<Vec<L>, Vec<R>> {
+    let (lefts, rights): (Vec<L>, Vec<R>) = partition_either(v);
+    if rights.is_empty() {
+        Left(lefts)
+    } else {
+        Right(rights)
+    }
+}
+
+/// Free-function form of [`Either::either`], for passing to higher-order
+/// code.
+pub fn either<L, R, T, F, G>(e: Either<L, R>, f: F, g: G) -> T
+where
+    F: FnOnce(L) -> T,
+    G: FnOnce(R) -> T,
+{
+    e.either(f, g)
+}
+
+/// Extension methods for iterators over `Result`s.
+pub trait ResultIteratorExt<L, R>: Iterator<Item = Result<L, R>> + Sized {
+    /// Collects every `Ok` into a `Left` if no item failed, otherwise
+    /// collects every `Err` into a `Right`.
+    fn collect_results_either(self) -> Either<Vec<L>, Vec<R>> {
+        let mut oks = Vec::new();
+        let mut errs = Vec::new();
+        for result in self {
+            match result {
+                Ok(l) if errs.is_empty() => oks.push(l),
+                Ok(_) => {}
+                Err(r) => errs.push(r),
+            }
+        }
+        if errs.is_empty() {
+            Left(oks)
+        } else {
+            Right(errs)
+        }
+    }
+}
+
+impl<L, R, I: Iterator<Item = Result<L, R>>> ResultIteratorExt<L, R> for I {}
+
+/// Iterator returned by [`EitherIteratorExt::swap_all`].
+pub type SwapAll<I, L, R> = core::iter::Map<I, fn(Either<L, R>) -> Either<R, L>>;
+
+/// Extension methods for iterators over `Either`s.
+pub trait EitherIteratorExt<L, R>: Iterator<Item = Either<L, R>> + Sized {
+    /// Lazily swaps the sides of every element.
+    fn swap_all(self) -> SwapAll<Self, L, R> {
+        self.map(Either::swap)
+    }
+
+    /// Returns `true` if every element is a `Left`, stopping at the first
+    /// `Right`. An empty iterator is all left.
+    fn all_left(mut self) -> bool {
+        self.all(|e| e.is_left())
+    }
+
+    /// Returns `true` if any element is a `Right`, stopping at the first.
+    fn any_right(mut self) -> bool {
+        self.any(|e| e.is_right())
+    }
+}
+
+impl<L, R, I: Iterator<Item = Either<L, R>>> EitherIteratorExt<L, R> for I {}
+
+/// Unwraps a `Left`, or returns the `Right` early from the enclosing
+/// function, like `?` for the left side.
+///
+/// ```
+/// use aurars::{either::Either, try_left};
+///
+/// fn double(e: Either<i32, &str>) -> Either<i32, &str> {
+///     let n = try_left!(e);
+///     Either::Left(n * 2)
+/// }
+/// assert_eq!(double(Either::Left(2)), Either::Left(4));
+/// assert_eq!(double(Either::Right("no")), Either::Right("no"));
+/// ```
+#[macro_export]
+macro_rules! try_left {
+    ($e:expr) => {
+        match $e {
+            $crate::either::Either::Left(value) => value,
+            $crate::either::Either::Right(other) => {
+                return $crate::either::Either::Right(other);
+            }
+        }
+    };
+}
+
+/// Unwraps a `Right`, or returns the `Left` early from the enclosing
+/// function, the mirror of [`try_left!`].
+#[macro_export]
+macro_rules! try_right {
+    ($e:expr) => {
+        match $e {
+            $crate::either::Either::Right(value) => value,
+            $crate::either::Either::Left(other) => {
+                return $crate::either::Either::Left(other);
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partition_with_capacity() {
+        let items = vec![Left(1), Right("a"), Left(2), Right("b"), Left(3)];
+        let (lefts, rights) = partition_either_with_capacity(items, 8, 4);
+        assert_eq!(lefts, vec![1, 2, 3]);
+        assert_eq!(rights, vec!["a", "b"]);
+        assert!(lefts.capacity() >= 8);
+        assert!(rights.capacity() >= 4);
+    }
+
+    #[test]
+    fn collect_results_all_ok() {
+        let items: Vec<Result<i32, String>> = vec![Ok(1), Ok(2), Ok(3)];
+        assert_eq!(
+            items.into_iter().collect_results_either(),
+            Left(vec![1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn collect_results_every_err() {
+        let items = vec![Ok(1), Err("a"), Ok(2), Err("b")];
+        assert_eq!(
+            items.into_iter().collect_results_either(),
+            Right(vec!["a", "b"])
+        );
+    }
+
+    #[test]
+    fn into_result_mapped_left() {
+        let e: Either<i32, &str> = Left(2);
+        assert_eq!(e.into_result_mapped(|n| n * 10, str::len), Ok(20));
+    }
+
+    #[test]
+    fn into_result_mapped_right() {
+        let e: Either<i32, &str> = Right("oops");
+        assert_eq!(e.into_result_mapped(|n| n * 10, str::len), Err(4));
+    }
+
+    #[test]
+    fn control_flow_round_trip() {
+        let flow: ControlFlow<&str, i32> = ControlFlow::Continue(1);
+        let either = Either::from(flow);
+        assert_eq!(either, Left(1));
+        assert_eq!(ControlFlow::from(either), ControlFlow::Continue(1));
+
+        let flow: ControlFlow<&str, i32> = ControlFlow::Break("done");
+        let either = Either::from(flow);
+        assert_eq!(either, Right("done"));
+        assert_eq!(ControlFlow::from(either), ControlFlow::Break("done"));
+    }
+
+    #[test]
+    fn debug_tag_names_side() {
+        let left: Either<i32, &str> = Left(7);
+        assert_eq!(left.debug_tag(), ("Left", "7".to_string()));
+        let right: Either<i32, &str> = Right("x");
+        assert_eq!(right.debug_tag(), ("Right", "\"x\"".to_string()));
+    }
+
+    #[test]
+    fn classify_slice_by_parity() {
+        let numbers = [1, 2, 3, 4];
+        let classified = classify_slice(&numbers, |n| n % 2 == 0);
+        assert_eq!(classified, vec![Right(&1), Left(&2), Right(&3), Left(&4)]);
+    }
+
+    #[test]
+    fn left_or_default_both_sides() {
+        assert_eq!(Left::<i32, &str>(4).left_or_default(), 4);
+        assert_eq!(Right::<i32, &str>("x").left_or_default(), 0);
+    }
+
+    #[test]
+    fn right_or_default_both_sides() {
+        assert_eq!(Left::<i32, String>(4).right_or_default(), "");
+        assert_eq!(Right::<i32, String>("x".into()).right_or_default(), "x");
+    }
+
+    #[test]
+    fn map_either_ref_keeps_original() {
+        let left: Either<String, Vec<i32>> = Left("aura".to_string());
+        assert_eq!(left.map_either_ref(String::len, Vec::len), Left(4));
+        assert_eq!(left, Left("aura".to_string()));
+
+        let right: Either<String, Vec<i32>> = Right(vec![1, 2]);
+        assert_eq!(right.map_either_ref(String::len, Vec::len), Right(2));
+        assert_eq!(right, Right(vec![1, 2]));
+    }
+
+    #[test]
+    fn try_unwrap_left_both_sides() {
+        assert_eq!(Left::<i32, &str>(1).try_unwrap_left(), Ok(1));
+        assert_eq!(Right::<i32, &str>("e").try_unwrap_left(), Err("e"));
+    }
+
+    #[test]
+    fn try_unwrap_right_both_sides() {
+        assert_eq!(Left::<i32, &str>(1).try_unwrap_right(), Err(1));
+        assert_eq!(Right::<i32, &str>("e").try_unwrap_right(), Ok("e"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bincode_round_trip_is_compact() {
+        use bincode::Options;
+
+        let options = bincode::DefaultOptions::new();
+        let left: Either<u8, String> = Left(7);
+        let bytes = options.serialize(&left).unwrap();
+        assert_eq!(bytes, vec![0, 7]);
+        assert_eq!(
+            options.deserialize::<Either<u8, String>>(&bytes).unwrap(),
+            left
+        );
+
+        let right: Either<u8, String> = Right("ok".to_string());
+        let bytes = options.serialize(&right).unwrap();
+        assert_eq!(bytes, vec![1, 2, b'o', b'k']);
+        assert_eq!(
+            options.deserialize::<Either<u8, String>>(&bytes).unwrap(),
+            right
+        );
+    }
+
+    #[test]
+    fn fold_left_into_right_both_sides() {
+        let left: Either<i32, String> = Left(3);
+        assert_eq!(left.fold_left_into_right(|n| n.to_string()), "3");
+        let right: Either<i32, String> = Right("r".to_string());
+        assert_eq!(right.fold_left_into_right(|n| n.to_string()), "r");
+    }
+
+    #[test]
+    fn swap_ref_points_into_original() {
+        let left: Either<i32, &str> = Left(1);
+        let Right(swapped) = left.swap_ref() else {
+            panic!("expected Right");
+        };
+        let Left(original) = &left else {
+            unreachable!();
+        };
+        assert!(std::ptr::eq(swapped, original));
+
+        let right: Either<i32, &str> = Right("r");
+        assert_eq!(right.swap_ref(), Left(&"r"));
+    }
+
+    #[test]
+    fn left_or_with_right_ref_both_sides() {
+        let left: Either<String, usize> = Left("aura".to_string());
+        assert_eq!(left.left_or_with_right_ref(|n| "x".repeat(*n)), "aura");
+        let right: Either<String, usize> = Right(3);
+        assert_eq!(right.left_or_with_right_ref(|n| "x".repeat(*n)), "xxx");
+        assert_eq!(right, Right(3));
+    }
+
+    #[test]
+    fn iter_any_downcasts() {
+        let left: Either<i32, String> = Left(5);
+        let items: Vec<&dyn Any> = left.iter_any().collect();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].downcast_ref::<i32>(), Some(&5));
+
+        let right: Either<i32, String> = Right("r".to_string());
+        let item = right.iter_any().next().unwrap();
+        assert!(item.downcast_ref::<i32>().is_none());
+        assert_eq!(item.downcast_ref::<String>().unwrap(), "r");
+    }
+
+    #[test]
+    fn get_or_insert_right_keeps_right() {
+        let mut e: Either<i32, i32> = Right(1);
+        *e.get_or_insert_right(5) += 10;
+        assert_eq!(e, Right(11));
+    }
+
+    #[test]
+    fn get_or_insert_right_converts_left() {
+        let mut e: Either<i32, i32> = Left(1);
+        *e.get_or_insert_right(5) += 10;
+        assert_eq!(e, Right(15));
+    }
+
+    #[test]
+    fn map_left_each_maps_elements() {
+        let left: Either<Vec<i32>, &str> = Left(vec![1, 2, 3]);
+        assert_eq!(left.map_left_each(|n| n * 10), Left(vec![10, 20, 30]));
+
+        let right: Either<Vec<i32>, &str> = Right("keep");
+        assert_eq!(right.map_left_each(|n| n * 10), Right("keep"));
+    }
+
+    #[test]
+    fn push_into_splits_stream() {
+        let (mut lefts, mut rights) = (vec![0], Vec::new());
+        for e in [Left(1), Right('a'), Left(2), Right('b')] {
+            e.push_into(&mut lefts, &mut rights);
+        }
+        assert_eq!(lefts, vec![0, 1, 2]);
+        assert_eq!(rights, vec!['a', 'b']);
+    }
+
+    #[test]
+    fn convert_through_newtypes() {
+        #[derive(Debug, PartialEq)]
+        struct Meters(f64);
+        impl From<f64> for Meters {
+            fn from(value: f64) -> Self {
+                Meters(value)
+            }
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Label(String);
+        impl From<&str> for Label {
+            fn from(value: &str) -> Self {
+                Label(value.to_string())
+            }
+        }
+
+        let left: Either<f64, &str> = Left(1.5);
+        assert_eq!(left.convert::<Meters, Label>(), Left(Meters(1.5)));
+        let right: Either<f64, &str> = Right("x");
+        assert_eq!(
+            right.convert::<Meters, Label>(),
+            Right(Label("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn select_indexes_table() {
+        let table = ["number", "text"];
+        assert_eq!(*Left::<i32, &str>(1).select(&table), "number");
+        assert_eq!(*Right::<i32, &str>("a").select(&table), "text");
+    }
+
+    #[test]
+    fn as_ref_keeps_original() {
+        let left: Either<String, i32> = Left("aura".to_string());
+        assert_eq!(left.as_ref().map_left(|s| s.len()), Left(4));
+        assert_eq!(left, Left("aura".to_string()));
+
+        let right: Either<String, i32> = Right(3);
+        assert_eq!(right.as_ref(), Right(&3));
+        assert_eq!(right, Right(3));
+    }
+
+    #[test]
+    fn as_mut_modifies_in_place() {
+        let mut left: Either<String, i32> = Left("au".to_string());
+        if let Left(s) = left.as_mut() {
+            s.push_str("ra");
+        }
+        assert_eq!(left, Left("aura".to_string()));
+
+        let mut right: Either<String, i32> = Right(3);
+        right.as_mut().map_right(|n| *n += 1);
+        assert_eq!(right, Right(4));
+    }
+
+    #[test]
+    fn either_collapses_both_sides() {
+        let left: Either<i32, &str> = Left(12);
+        assert_eq!(left.either(|n| n as usize, str::len), 12);
+        let right: Either<i32, &str> = Right("four");
+        assert_eq!(right.either(|n| n as usize, str::len), 4);
+    }
+
+    #[test]
+    fn collect_left_fills_left() {
+        let collected: Either<Vec<i32>, String> = collect_left(1..=3);
+        assert_eq!(collected, Left(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn collect_right_fills_right() {
+        let collected: Either<String, Vec<char>> = collect_right("ab".chars());
+        assert_eq!(collected, Right(vec!['a', 'b']));
+    }
+
+    #[test]
+    fn into_iter_yields_single_value() {
+        let left: Either<i32, i32> = Left(1);
+        assert_eq!(left.into_iter().collect::<Vec<_>>(), vec![1]);
+        let right: Either<i32, i32> = Right(2);
+        assert_eq!(right.iter().collect::<Vec<_>>(), vec![&2]);
+        assert_eq!([Left(1), Right(2)].into_iter().flatten().sum::<i32>(), 3);
+    }
+
+    #[test]
+    fn into_either_iter_sums_active_side() {
+        let left: Either<Vec<i32>, std::ops::Range<i32>> = Left(vec![1, 2, 3]);
+        let right: Either<Vec<i32>, std::ops::Range<i32>> = Right(0..3);
+        assert_eq!(left.into_either_iter().sum::<i32>(), 6);
+        assert_eq!(right.into_either_iter().sum::<i32>(), 3);
+    }
+
+    #[test]
+    fn into_either_iter_forwards_size_hint() {
+        let left: Either<Vec<i32>, std::ops::Range<i32>> = Left(vec![1, 2, 3]);
+        assert_eq!(left.into_either_iter().size_hint(), (3, Some(3)));
+        let right: Either<Vec<i32>, std::iter::Skip<std::ops::Range<i32>>> = Right((0..10).skip(4));
+        assert_eq!(right.into_either_iter().size_hint(), (6, Some(6)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip_is_externally_tagged() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Point {
+            x: i32,
+            y: i32,
+        }
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Name {
+            name: String,
+        }
+
+        let left: Either<Point, Name> = Left(Point { x: 1, y: 2 });
+        let json = serde_json::to_string(&left).unwrap();
+        assert_eq!(json, r#"{"Left":{"x":1,"y":2}}"#);
+        assert_eq!(
+            serde_json::from_str::<Either<Point, Name>>(&json).unwrap(),
+            left
+        );
+
+        let right: Either<Point, Name> = Right(Name {
+            name: "aura".into(),
+        });
+        let json = serde_json::to_string(&right).unwrap();
+        assert_eq!(json, r#"{"Right":{"name":"aura"}}"#);
+        assert_eq!(
+            serde_json::from_str::<Either<Point, Name>>(&json).unwrap(),
+            right
+        );
+
+        assert!(serde_json::from_str::<Either<Point, Name>>(r#"{"Middle":{}}"#).is_err());
+    }
+
+    #[test]
+    fn display_forwards_active_side() {
+        assert_eq!(Left::<i32, &str>(5).to_string(), "5");
+        assert_eq!(Right::<i32, &str>("x").to_string(), "x");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn error_preserves_source_chain() {
+        use std::error::Error;
+
+        #[derive(Debug)]
+        struct Root;
+        impl Display for Root {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("root cause")
+            }
+        }
+        impl Error for Root {}
+
+        #[derive(Debug)]
+        struct Wrapper(Root);
+        impl Display for Wrapper {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("wrapper")
+            }
+        }
+        impl Error for Wrapper {
+            fn source(&self) -> Option<&(dyn Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let err: Box<dyn Error> = Box::new(Either::<Wrapper, std::fmt::Error>::Left(Wrapper(Root)));
+        assert_eq!(err.to_string(), "wrapper");
+        assert_eq!(err.source().unwrap().to_string(), "root cause");
+
+        let err: Either<Wrapper, std::fmt::Error> = Right(std::fmt::Error);
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn flatten_options_all_cases() {
+        assert_eq!(
+            Left::<_, Option<&str>>(Some(1)).flatten_options(),
+            Some(Left(1))
+        );
+        assert_eq!(
+            Left::<Option<i32>, Option<&str>>(None).flatten_options(),
+            None
+        );
+        assert_eq!(
+            Right::<Option<i32>, _>(Some("r")).flatten_options(),
+            Some(Right("r"))
+        );
+        assert_eq!(
+            Right::<Option<i32>, Option<&str>>(None).flatten_options(),
+            None
+        );
+    }
+
+    #[test]
+    fn flatten_left_nesting() {
+        let nested: Either<Either<i32, &str>, &str> = Left(Left(1));
+        assert_eq!(nested.flatten(), Left(1));
+        let nested: Either<Either<i32, &str>, &str> = Left(Right("inner"));
+        assert_eq!(nested.flatten(), Right("inner"));
+        let nested: Either<Either<i32, &str>, &str> = Right("outer");
+        assert_eq!(nested.flatten(), Right("outer"));
+    }
+
+    #[test]
+    fn flatten_right_nesting() {
+        let nested: Either<i32, Either<i32, &str>> = Left(1);
+        assert_eq!(nested.flatten_right(), Left(1));
+        let nested: Either<i32, Either<i32, &str>> = Right(Left(2));
+        assert_eq!(nested.flatten_right(), Left(2));
+        let nested: Either<i32, Either<i32, &str>> = Right(Right("inner"));
+        assert_eq!(nested.flatten_right(), Right("inner"));
+    }
+
+    #[cfg(feature = "anyhow")]
+    #[test]
+    fn into_anyhow_converts_right_error() {
+        let ok: Either<i32, std::fmt::Error> = Left(1);
+        assert_eq!(ok.into_anyhow().unwrap(), 1);
+
+        let err: Either<i32, std::fmt::Error> = Right(std::fmt::Error);
+        let result = err.into_anyhow();
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .downcast_ref::<std::fmt::Error>()
+            .is_some());
+    }
+
+    #[test]
+    fn partition_either_splits_mixed() {
+        let items = vec![
+            Left(1),
+            Right("a".to_string()),
+            Left(2),
+            Right("b".to_string()),
+        ];
+        let (lefts, rights): (Vec<i32>, Vec<String>) = partition_either(items);
+        assert_eq!(lefts, vec![1, 2]);
+        assert_eq!(rights, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn transpose_all_cases() {
+        type Nested = Either<Option<i32>, Option<char>>;
+        assert_eq!(Nested::Left(Some(1)).transpose(), Some(Left(1)));
+        assert_eq!(Nested::Left(None).transpose(), None);
+        assert_eq!(Nested::Right(Some('r')).transpose(), Some(Right('r')));
+        assert_eq!(Nested::Right(None).transpose(), None);
+    }
+
+    #[test]
+    fn context_only_attaches_to_right() {
+        let left: Either<i32, &str> = Left(1);
+        assert_eq!(left.context("parsing"), Left(1));
+        let right: Either<i32, &str> = Right("bad digit");
+        assert_eq!(right.context("parsing"), Right(("parsing", "bad digit")));
+    }
+
+    #[test]
+    fn with_context_is_lazy() {
+        let left: Either<i32, &str> = Left(1);
+        assert_eq!(left.with_context(|| -> String { unreachable!() }), Left(1));
+        let right: Either<i32, &str> = Right("bad digit");
+        assert_eq!(
+            right.with_context(|| format!("line {}", 3)),
+            Right(("line 3".to_string(), "bad digit"))
+        );
+    }
+
+    mod macros_without_prelude {
+        use crate::either::Either;
+
+        fn sum_lefts(
+            a: Either<i32, &'static str>,
+            b: Either<i32, &'static str>,
+        ) -> Either<i32, &'static str> {
+            Either::Left(try_left!(a) + try_left!(b))
+        }
+
+        fn concat_rights(a: Either<i32, String>, b: Either<i32, String>) -> Either<i32, String> {
+            Either::Right(try_right!(a) + &try_right!(b))
+        }
+
+        #[test]
+        fn try_left_unwraps_or_returns() {
+            assert_eq!(sum_lefts(Either::Left(1), Either::Left(2)), Either::Left(3));
+            assert_eq!(
+                sum_lefts(Either::Left(1), Either::Right("stop")),
+                Either::Right("stop")
+            );
+        }
+
+        #[test]
+        fn try_right_unwraps_or_returns() {
+            let a = Either::Right("au".to_string());
+            let b = Either::Right("ra".to_string());
+            assert_eq!(concat_rights(a, b), Either::Right("aura".to_string()));
+            assert_eq!(
+                concat_rights(Either::Left(7), Either::Right("x".into())),
+                Either::Left(7)
+            );
+        }
+    }
+
+    #[test]
+    fn take_left_from_left() {
+        let mut e: Either<Vec<i32>, &str> = Left(vec![1, 2]);
+        assert_eq!(e.take_left(), Some(vec![1, 2]));
+        assert_eq!(e, Left(vec![]));
+    }
+
+    #[test]
+    fn take_left_from_right() {
+        let mut e: Either<Vec<i32>, &str> = Right("r");
+        assert_eq!(e.take_left(), None);
+        assert_eq!(e, Right("r"));
+    }
+
+    #[test]
+    fn take_right_both_states() {
+        let mut e: Either<u8, String> = Right("x".into());
+        assert_eq!(e.take_right(), Some("x".to_string()));
+        assert_eq!(e, Right(String::new()));
+
+        let mut e: Either<u8, String> = Left(3);
+        assert_eq!(e.take_right(), None);
+        assert_eq!(e, Left(3));
+    }
+
+    #[test]
+    fn zip_with_combines_lefts() {
+        let a: Either<i32, &str> = Left(2);
+        assert_eq!(a.zip_with(Left(5), |x, y| x * y), Left(10));
+    }
+
+    #[test]
+    fn zip_with_short_circuits_on_rights() {
+        let left: Either<i32, &str> = Left(1);
+        let right: Either<i32, &str> = Right("first");
+        assert_eq!(right.zip_with(left, |x, y| x + y), Right("first"));
+        assert_eq!(
+            left.zip_with(Right("second"), |x, y: i32| x + y),
+            Right("second")
+        );
+        assert_eq!(
+            right.zip_with(Right("second"), |x, y: i32| x + y),
+            Right("first")
+        );
+    }
+
+    #[test]
+    fn swap_all_flips_each_element() {
+        let items: Vec<Either<i32, &str>> = vec![Left(1), Right("a"), Left(2)];
+        let swapped: Vec<_> = items.into_iter().swap_all().collect();
+        assert_eq!(swapped, vec![Right(1), Left("a"), Right(2)]);
+    }
+
+    #[test]
+    fn all_left_and_any_right() {
+        let all_left: [Either<i32, i32>; 2] = [Left(1), Left(2)];
+        let mixed: [Either<i32, i32>; 2] = [Left(1), Right(2)];
+        let all_right: [Either<i32, i32>; 2] = [Right(1), Right(2)];
+
+        assert!(all_left.into_iter().all_left());
+        assert!(!all_left.into_iter().any_right());
+        assert!(!mixed.into_iter().all_left());
+        assert!(mixed.into_iter().any_right());
+        assert!(!all_right.into_iter().all_left());
+        assert!(all_right.into_iter().any_right());
+        assert!(std::iter::empty::<Either<(), ()>>().all_left());
+    }
+
+    #[test]
+    fn iter_mut_mutates_either_side() {
+        let mut left: Either<i32, i32> = Left(1);
+        let mut right: Either<i32, i32> = Right(10);
+        for e in [&mut left, &mut right] {
+            for x in e.iter_mut() {
+                *x += 1;
+            }
+        }
+        assert_eq!((left, right), (Left(2), Right(11)));
+    }
+
+    #[test]
+    fn iter_and_iter_mut_yield_one_element() {
+        let mut e: Either<&str, &str> = Right("only");
+        assert_eq!(e.iter().count(), 1);
+        assert_eq!(e.iter_mut().count(), 1);
+    }
+
+    #[test]
+    fn replace_with_swaps_sides_in_place() {
+        let mut e: Either<String, String> = Left("aura".to_string());
+        e.replace_with(Either::swap);
+        assert_eq!(e, Right("aura".to_string()));
+        e.replace_with(|e| e.swap().map_left(|s| s.to_uppercase()));
+        assert_eq!(e, Left("AURA".to_string()));
+    }
+
+    #[test]
+    fn cloned_and_copied_lift_references() {
+        let left: Either<String, u8> = Left("aura".to_string());
+        let right: Either<String, u8> = Right(5);
+        assert_eq!(left.as_ref().cloned(), left);
+        assert_eq!(right.as_ref().cloned(), right);
+
+        let left: Either<i32, char> = Left(4);
+        let right: Either<i32, char> = Right('r');
+        assert_eq!(left.as_ref().copied(), Left(4));
+        assert_eq!(right.as_ref().copied(), Right('r'));
+    }
+
+    #[test]
+    fn filter_left_cases() {
+        let even = |n: &i32| n % 2 == 0;
+        assert_eq!(Either::<i32, &str>::Left(4).filter_left(even), Some(4));
+        assert_eq!(Either::<i32, &str>::Left(3).filter_left(even), None);
+        assert_eq!(Either::<i32, &str>::Right("r").filter_left(even), None);
+    }
+
+    #[test]
+    fn filter_right_cases() {
+        let short = |s: &&str| s.len() < 3;
+        assert_eq!(
+            Either::<i32, &str>::Right("ok").filter_right(short),
+            Some("ok")
+        );
+        assert_eq!(Either::<i32, &str>::Right("long").filter_right(short), None);
+        assert_eq!(Either::<i32, &str>::Left(1).filter_right(short), None);
+    }
+
+    #[test]
+    fn lift_maps_lefts_only() {
+        let double = lift(|n: i32| n * 2);
+        assert_eq!(double(Left(21)), Left::<i32, &str>(42));
+        assert_eq!(double(Right("skip")), Right("skip"));
+    }
+
+    #[test]
+    fn factor_first_pulls_shared_key() {
+        let left: Either<(i32, &str), (i32, i32)> = Left((1, "a"));
+        let right: Either<(i32, &str), (i32, i32)> = Right((1, 2));
+        assert_eq!(left.factor_first(), (1, Left("a")));
+        assert_eq!(right.factor_first(), (1, Right(2)));
+    }
+
+    #[test]
+    fn factor_second_pulls_shared_key() {
+        let left: Either<(&str, i32), (i32, i32)> = Left(("a", 1));
+        let right: Either<(&str, i32), (i32, i32)> = Right((2, 1));
+        assert_eq!(left.factor_second(), (Left("a"), 1));
+        assert_eq!(right.factor_second(), (Right(2), 1));
+    }
+
+    #[test]
+    fn free_either_in_pipe() {
+        let describe = |e: Either<usize, &str>| {
+            crate::pipe!(e
+                => |e| either(e, |n| n * 2, str::len)
+                => |n| n + 1)
+            .into_inner()
+        };
+        assert_eq!(describe(Left(4)), 9);
+        assert_eq!(describe(Right("aura")), 5);
+
+        let partial = crate::pipe!(Right::<usize, &str>("ab") => either(|n| n, str::len));
+        assert_eq!(partial.into_inner(), 2);
+    }
+
+    #[test]
+    fn zip_all_collects_lefts() {
+        let items: [Either<i32, &str>; 3] = [Left(1), Left(2), Left(3)];
+        assert_eq!(zip_all(&items), Left(vec![1, 2, 3]));
+        assert_eq!(zip_all::<i32, &str>(&[]), Left(vec![]));
+    }
+
+    #[test]
+    fn zip_all_returns_first_right() {
+        let items: [Either<i32, &str>; 4] = [Left(1), Right("first"), Left(3), Right("second")];
+        assert_eq!(zip_all(&items), Right("first"));
+    }
+
+    #[test]
+    fn cartesian_pairs_all_lefts() {
+        let xs: Either<Vec<i32>, &str> = Left(vec![1, 2]);
+        let ys: Either<Vec<char>, &str> = Left(vec!['a', 'b', 'c']);
+        assert_eq!(
+            xs.cartesian(ys),
+            Left(vec![
+                (1, 'a'),
+                (1, 'b'),
+                (1, 'c'),
+                (2, 'a'),
+                (2, 'b'),
+                (2, 'c')
+            ])
+        );
+    }
+
+    #[test]
+    fn cartesian_short_circuits_on_right() {
+        let xs: Either<Vec<i32>, &str> = Left(vec![1, 2]);
+        let none: Either<Vec<char>, &str> = Right("missing");
+        assert_eq!(xs.cartesian(none), Right("missing"));
+        let first: Either<Vec<i32>, &str> = Right("first");
+        assert_eq!(
+            first.cartesian(Right::<Vec<char>, _>("second")),
+            Right("first")
+        );
+    }
+
+    #[test]
+    fn ok_or_left_cases() {
+        assert_eq!(Either::ok_or_left(Some(1), "missing"), Left(1));
+        assert_eq!(
+            Either::<i32, _>::ok_or_left(None, "missing"),
+            Right("missing")
+        );
+        assert_eq!(
+            Either::ok_or_else_left(Some(1), || unreachable!()),
+            Left::<_, &str>(1)
+        );
+        assert_eq!(
+            Either::<i32, _>::ok_or_else_left(None, || "lazy"),
+            Right("lazy")
+        );
+    }
+
+    #[test]
+    fn ok_or_right_cases() {
+        assert_eq!(Either::ok_or_right(Some('r'), 0), Right('r'));
+        assert_eq!(Either::<_, char>::ok_or_right(None, 0), Left(0));
+        assert_eq!(
+            Either::ok_or_else_right(Some('r'), || unreachable!()),
+            Right::<i32, _>('r')
+        );
+        assert_eq!(Either::<_, char>::ok_or_else_right(None, || 7), Left(7));
+    }
+
+    #[test]
+    fn partition_indexed_keeps_positions() {
+        let items: Vec<Either<i32, &str>> = vec![Right("a"), Left(1), Left(2), Right("b")];
+        let (lefts, rights) = partition_indexed(items);
+        assert_eq!(lefts, vec![(1, 1), (2, 2)]);
+        assert_eq!(rights, vec![(0, "a"), (3, "b")]);
+    }
+
+    #[test]
+    fn swap_in_place_toggles_side() {
+        let mut e: Either<String, String> = Left("payload".to_string());
+        e.swap_in_place();
+        assert_eq!(e, Right("payload".to_string()));
+        e.swap_in_place();
+        assert_eq!(e, Left("payload".to_string()));
+    }
+
+    #[test]
+    fn partition_either_indexed_mixed_sequence() {
+        let items = (0..6).map(|n| if n % 3 == 0 { Right(n * 10) } else { Left(n) });
+        let (lefts, rights) = partition_either_indexed(items);
+        assert_eq!(lefts, vec![(1, 1), (2, 2), (4, 4), (5, 5)]);
+        assert_eq!(rights, vec![(0, 0), (3, 30)]);
+    }
+
+    #[test]
+    fn as_deref_borrows_targets() {
+        let left: Either<String, Vec<i32>> = Left("aura".to_string());
+        let right: Either<String, Vec<i32>> = Right(vec![1, 2]);
+        assert_eq!(left.as_deref(), Left::<&str, &[i32]>("aura"));
+        assert_eq!(right.as_deref(), Right::<&str, &[i32]>(&[1, 2]));
+    }
+
+    #[test]
+    fn as_deref_mut_edits_in_place() {
+        let mut e: Either<String, Vec<i32>> = Right(vec![3, 1, 2]);
+        if let Right(slice) = e.as_deref_mut() {
+            slice.sort();
+        }
+        assert_eq!(e, Right(vec![1, 2, 3]));
+
+        let mut e: Either<String, Vec<i32>> = Left("aura".to_string());
+        if let Left(s) = e.as_deref_mut() {
+            s.make_ascii_uppercase();
+        }
+        assert_eq!(e, Left("AURA".to_string()));
+    }
+
+    #[test]
+    fn neg_applies_to_active_side() {
+        let left: Either<i32, f64> = Left(3);
+        let right: Either<i32, f64> = Right(1.5);
+        assert_eq!(-left, Left(-3));
+        assert_eq!(-right, Right(-1.5));
+    }
+
+    #[test]
+    fn unwrap_with_skips_message_on_success() {
+        let left: Either<i32, &str> = Left(1);
+        let right: Either<i32, &str> = Right("r");
+        assert_eq!(left.unwrap_left_with(|_| unreachable!()), 1);
+        assert_eq!(right.unwrap_right_with(|_| unreachable!()), "r");
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a left, got right `boom`")]
+    fn unwrap_left_with_panics_with_message() {
+        let e: Either<i32, &str> = Right("boom");
+        e.unwrap_left_with(|r| format!("expected a left, got right `{r}`"));
+    }
+
+    #[test]
+    #[should_panic(expected = "left 42 is not a right")]
+    fn unwrap_right_with_panics_with_message() {
+        let e: Either<i32, &str> = Left(42);
+        e.unwrap_right_with(|l| format!("left {l} is not a right"));
+    }
+
+    #[test]
+    fn sequence_or_rights_all_left() {
+        let v: Vec<Either<i32, &str>> = vec![Left(1), Left(2)];
+        assert_eq!(sequence_or_rights(v), Left(vec![1, 2]));
+    }
+
+    #[test]
+    fn sequence_or_rights_mixed_keeps_rights() {
+        let v = vec![Left(1), Right("a"), Left(2), Right("b")];
+        assert_eq!(sequence_or_rights(v), Right(vec!["a", "b"]));
+    }
+
+    #[test]
+    fn sequence_or_rights_all_right() {
+        let v: Vec<Either<i32, &str>> = vec![Right("x"), Right("y")];
+        assert_eq!(sequence_or_rights(v), Right(vec!["x", "y"]));
+    }
+
+    #[test]
+    fn zip_pairs_lefts() {
+        let a: Either<i32, &str> = Left(1);
+        let b: Either<char, u8> = Left('x');
+        assert_eq!(a.zip(b), Some(Left((1, 'x'))));
+    }
+
+    #[test]
+    fn zip_pairs_rights() {
+        let a: Either<i32, &str> = Right("r");
+        let b: Either<char, u8> = Right(7);
+        assert_eq!(a.zip(b), Some(Right(("r", 7))));
+    }
+
+    #[test]
+    fn zip_mismatch_is_none() {
+        let a: Either<i32, &str> = Left(1);
+        let b: Either<char, u8> = Right(7);
+        assert_eq!(a.zip(b), None);
+        assert_eq!(b.zip(a), None);
+    }
+
+    #[test]
+    fn hash_distinguishes_sides() {
+        use std::collections::HashSet;
+
+        let set: HashSet<Either<i32, i32>> = [Left(0), Right(0), Left(0)].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Left(0)));
+        assert!(set.contains(&Right(0)));
+    }
+
+    #[test]
+    fn default_is_left_default() {
+        assert_eq!(Either::<i32, &str>::default(), Left(0));
+    }
+
+    #[test]
+    fn split_routes_by_predicate() {
+        let is_even = |n: &u32| n.is_multiple_of(2);
+        assert_eq!(Either::split(4, is_even), Left(4));
+        assert_eq!(Either::split(7, is_even), Right(7));
+
+        let (evens, odds): (Vec<u32>, Vec<u32>) =
+            partition_either((1..=6).map(|n| Either::split(n, is_even)));
+        assert_eq!(evens, [2, 4, 6]);
+        assert_eq!(odds, [1, 3, 5]);
+    }
+
+    #[test]
+    fn as_ref_forwards_to_active_side() {
+        fn len(s: impl AsRef<str>) -> usize {
+            s.as_ref().len()
+        }
+        let owned: Either<String, &str> = Left(String::from("aura"));
+        let borrowed: Either<String, &str> = Right("rs");
+        assert_eq!(AsRef::<str>::as_ref(&borrowed), "rs");
+        assert_eq!(len(owned), 4);
+        assert_eq!(len(borrowed), 2);
+    }
+}
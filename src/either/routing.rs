@@ -0,0 +1,29 @@
+//! Sending values to one side of an [`Either`] or the other.
+
+use super::Either::{self, Left, Right};
+
+/// Routes `value` to `Left` if its key is below `threshold`, otherwise to
+/// `Right`.
+pub fn by_key<V, K: Ord>(value: V, key: impl Fn(&V) -> K, threshold: K) -> Either<V, V> {
+    if key(&value) < threshold {
+        Left(value)
+    } else {
+        Right(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn by_key_below_threshold_is_left() {
+        assert_eq!(by_key("ab", |s| s.len(), 3), Left("ab"));
+    }
+
+    #[test]
+    fn by_key_at_or_above_threshold_is_right() {
+        assert_eq!(by_key("abc", |s| s.len(), 3), Right("abc"));
+        assert_eq!(by_key("abcd", |s| s.len(), 3), Right("abcd"));
+    }
+}
@@ -0,0 +1,56 @@
+//! Deferred computations.
+
+use core::marker::PhantomData;
+
+/// A value computed by a thunk only when forced.
+pub struct Lazy<T, F: FnOnce() -> T> {
+    thunk: F,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, F: FnOnce() -> T> Lazy<T, F> {
+    /// Defers `thunk` until the value is forced.
+    pub fn new(thunk: F) -> Self {
+        Lazy {
+            thunk,
+            _value: PhantomData,
+        }
+    }
+
+    /// Runs the thunk and returns its value.
+    pub fn force(self) -> T {
+        (self.thunk)()
+    }
+
+    /// Composes `g` after the thunk without running either of them.
+    pub fn map<U, G: FnOnce(T) -> U>(self, g: G) -> Lazy<U, impl FnOnce() -> U> {
+        Lazy::new(move || g(self.force()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn force_runs_thunk() {
+        assert_eq!(Lazy::new(|| 6 * 7).force(), 42);
+    }
+
+    #[test]
+    fn map_preserves_laziness() {
+        let runs = Cell::new(0);
+        let lazy = Lazy::new(|| {
+            runs.set(runs.get() + 1);
+            20
+        })
+        .map(|x| {
+            runs.set(runs.get() + 1);
+            x + 1
+        });
+        assert_eq!(runs.get(), 0);
+        assert_eq!(lazy.force(), 21);
+        assert_eq!(runs.get(), 2);
+    }
+}
@@ -1,3 +1,13 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod either;
+pub mod lazy;
+pub mod pipe;
+pub mod prelude;
+pub mod recur;
+
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
 }
@@ -0,0 +1,1384 @@
+//! Left-to-right function application.
+
+use core::fmt::Debug;
+use core::future::Future;
+use core::ops::{ControlFlow, Deref, DerefMut};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+
+use alloc::{format, string::String, vec::Vec};
+
+use crate::either::Either;
+
+/// Wraps a value so that functions can be applied to it in a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Pipe<T>(T);
+
+impl<T> Pipe<T> {
+    /// Wraps `value` in a pipe.
+    pub fn new(value: T) -> Self {
+        Pipe(value)
+    }
+
+    /// Applies `f` to the inner value and continues the chain.
+    pub fn pipe<B, F: FnOnce(T) -> B>(self, f: F) -> Pipe<B> {
+        Pipe(f(self.0))
+    }
+
+    /// Unwraps the inner value, ending the chain.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+
+    /// Runs a fallible check against the inner value, passing the pipe
+    /// through on `Ok` and short-circuiting with the error otherwise.
+    pub fn try_tap<E, F: FnOnce(&T) -> Result<(), E>>(self, f: F) -> Result<Pipe<T>, E> {
+        f(&self.0)?;
+        Ok(self)
+    }
+
+    /// Continues with the pipe if `pred` holds for the inner value, otherwise
+    /// fails with `err`.
+    pub fn guard<E>(self, pred: impl FnOnce(&T) -> bool, err: E) -> Result<Pipe<T>, E> {
+        if pred(&self.0) {
+            Ok(self)
+        } else {
+            Err(err)
+        }
+    }
+
+    /// Applies each of `fns` to a borrow of the inner value, collecting the
+    /// outputs without consuming the pipe.
+    pub fn fan_out<B>(&self, fns: &[&dyn Fn(&T) -> B]) -> Vec<B> {
+        fns.iter().map(|f| f(&self.0)).collect()
+    }
+
+    /// Wraps the inner value in `Ok` if `pred` holds, or replaces it with
+    /// `Err(err)` otherwise, keeping the result inside the pipe.
+    pub fn require<E>(self, pred: impl FnOnce(&T) -> bool, err: E) -> Pipe<Result<T, E>> {
+        if pred(&self.0) {
+            Pipe(Ok(self.0))
+        } else {
+            Pipe(Err(err))
+        }
+    }
+
+    /// Applies `f` like [`pipe`](Pipe::pipe), printing `label` and how long
+    /// `f` took to stderr.
+    #[cfg(feature = "std")]
+    pub fn timed<B, F: FnOnce(T) -> B>(self, label: &str, f: F) -> Pipe<B> {
+        self.timed_to(&mut std::io::stderr(), label, f)
+    }
+
+    /// Like [`timed`](Pipe::timed), but writes the `label: duration` line to
+    /// `out`. Write errors are ignored so timing never interrupts the chain.
+    #[cfg(feature = "std")]
+    pub fn timed_to<W, B, F>(self, out: &mut W, label: &str, f: F) -> Pipe<B>
+    where
+        W: std::io::Write,
+        F: FnOnce(T) -> B,
+    {
+        let start = std::time::Instant::now();
+        let value = f(self.0);
+        let _ = writeln!(out, "{}: {:?}", label, start.elapsed());
+        Pipe(value)
+    }
+
+    /// Prints the inner value to stderr with `{:?}` and passes the pipe on
+    /// unchanged.
+    #[cfg(feature = "std")]
+    pub fn inspect(self) -> Pipe<T>
+    where
+        T: Debug,
+    {
+        eprintln!("{:?}", self.0);
+        self
+    }
+
+    /// Like [`inspect`](Pipe::inspect), but prefixes the value with
+    /// `label: `.
+    ///
+    /// ```
+    /// use aurars::pipe::Pipe;
+    ///
+    /// let n = Pipe::new(4)
+    ///     .pipe(|x| x * 2)
+    ///     .inspect_labeled("doubled")
+    ///     .pipe(|x| x + 1);
+    /// assert_eq!(n.into_inner(), 9);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn inspect_labeled(self, label: &str) -> Pipe<T>
+    where
+        T: Debug,
+    {
+        eprintln!("{}: {:?}", label, self.0);
+        self
+    }
+
+    /// Applies a fallible `f`, continuing the chain on `Ok` and returning the
+    /// error otherwise. The result can be chained further with `?`.
+    pub fn try_map<B, E, F: FnOnce(T) -> Result<B, E>>(self, f: F) -> Result<Pipe<B>, E> {
+        f(self.0).map(Pipe)
+    }
+
+    /// Splits the chain in two: `on_true` continues it as a `Left` pipe when
+    /// `pred` holds, and `on_false` continues it as a `Right` pipe otherwise.
+    pub fn branch_map<L, R, FL, FR>(
+        self,
+        pred: impl FnOnce(&T) -> bool,
+        on_true: FL,
+        on_false: FR,
+    ) -> Either<Pipe<L>, Pipe<R>>
+    where
+        FL: FnOnce(T) -> L,
+        FR: FnOnce(T) -> R,
+    {
+        if pred(&self.0) {
+            Either::Left(self.pipe(on_true))
+        } else {
+            Either::Right(self.pipe(on_false))
+        }
+    }
+
+    /// Runs `f` against a borrow of the inner value and passes the pipe on
+    /// unchanged.
+    pub fn tap<F: FnOnce(&T)>(self, f: F) -> Pipe<T> {
+        f(&self.0);
+        self
+    }
+
+    /// Computes a value from a borrow of the inner value, leaving the pipe
+    /// in place.
+    pub fn pipe_ref<B, F: FnOnce(&T) -> B>(&self, f: F) -> B {
+        f(&self.0)
+    }
+
+    /// Mutates the inner value in place and continues the chain.
+    pub fn pipe_mut<F: FnOnce(&mut T)>(mut self, f: F) -> Pipe<T> {
+        f(&mut self.0);
+        self
+    }
+
+    /// Applies `f` to a borrow of the inner value, reusing the result stored
+    /// in `cache` for an equal input and storing it there otherwise.
+    ///
+    /// The cache is not borrowed while `f` runs, so `f` may use it too.
+    #[cfg(feature = "std")]
+    pub fn memo_pipe<B, F>(self, cache: &std::cell::RefCell<HashMap<T, B>>, f: F) -> Pipe<B>
+    where
+        T: Eq + Hash + Clone,
+        B: Clone,
+        F: Fn(&T) -> B,
+    {
+        if let Some(hit) = cache.borrow().get(&self.0) {
+            return Pipe(hit.clone());
+        }
+        let value = f(&self.0);
+        cache.borrow_mut().insert(self.0, value.clone());
+        Pipe(value)
+    }
+
+    /// Ends the chain by looping: the inner value is the initial state for
+    /// [`recur`](crate::recur::recur), and the break value is returned.
+    pub fn recur<B, F: FnMut(T) -> ControlFlow<B, T>>(self, f: F) -> B {
+        crate::recur::recur(self.0, f)
+    }
+
+    /// Applies an asynchronous `f` to the inner value, continuing the chain
+    /// once its future resolves.
+    pub async fn pipe_async<B, Fut, F>(self, f: F) -> Pipe<B>
+    where
+        F: FnOnce(T) -> Fut,
+        Fut: Future<Output = B>,
+    {
+        Pipe(f(self.0).await)
+    }
+}
+
+impl<T: IntoIterator> Pipe<T> {
+    /// Pairs each item of the inner iterable with its index.
+    pub fn enumerate_pipe(self) -> Pipe<Vec<(usize, T::Item)>> {
+        Pipe(self.0.into_iter().enumerate().collect())
+    }
+
+    /// Maps each item to an iterable and flattens the results.
+    pub fn flat_map_pipe<U, I, F>(self, f: F) -> Pipe<Vec<U>>
+    where
+        I: IntoIterator<Item = U>,
+        F: FnMut(T::Item) -> I,
+    {
+        Pipe(self.0.into_iter().flat_map(f).collect())
+    }
+
+    /// Reduces the items with `f`, yielding `None` for an empty iterable.
+    pub fn reduce_pipe<F>(self, f: F) -> Pipe<Option<T::Item>>
+    where
+        F: FnMut(T::Item, T::Item) -> T::Item,
+    {
+        Pipe(self.0.into_iter().reduce(f))
+    }
+
+    /// Ends the chain by extending `target` with the items.
+    pub fn collect_into<C: Extend<T::Item>>(self, target: &mut C) {
+        target.extend(self.0);
+    }
+
+    /// Maps each item with `f`, which can update a running state starting at
+    /// `init`. Returns the outputs as a pipe along with the final state.
+    pub fn scan_with_state<St, U, F>(self, init: St, mut f: F) -> (Pipe<Vec<U>>, St)
+    where
+        St: Clone,
+        F: FnMut(&mut St, T::Item) -> U,
+    {
+        let mut state = init;
+        let outputs = self.0.into_iter().map(|item| f(&mut state, item)).collect();
+        (Pipe(outputs), state)
+    }
+}
+
+impl<T> Pipe<Vec<T>> {
+    /// Reserves room for at least `n` elements in total, as a hint for
+    /// later stages.
+    ///
+    /// The hint lives in the vector's allocation, so stages that work in
+    /// place (sorting, dedup, [`take_pipe`](Pipe::take_pipe) and the like)
+    /// keep it. Only [`interleave_pipe`](Pipe::interleave_pipe) and
+    /// [`map_while_pipe`](Pipe::map_while_pipe) preallocate their output
+    /// from it; other collecting stages size their output themselves.
+    /// Capacity beyond the vector's length counts as a hint, and without
+    /// one those two stages allocate as they otherwise would. Results are the
+    /// same with or without a hint.
+    pub fn with_capacity(mut self, n: usize) -> Pipe<Vec<T>> {
+        self.0.reserve(n.saturating_sub(self.0.len()));
+        self
+    }
+
+    /// The capacity reserved beyond the elements, as left by
+    /// [`with_capacity`](Pipe::with_capacity).
+    fn capacity_hint(&self) -> Option<usize> {
+        (self.0.capacity() > self.0.len()).then_some(self.0.capacity())
+    }
+
+    /// Applies `f` to each `size`-element chunk of the vector, collecting the
+    /// results. The final chunk may be shorter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn chunked<B, F: FnMut(&[T]) -> B>(self, size: usize, f: F) -> Pipe<Vec<B>> {
+        Pipe(self.0.chunks(size).map(f).collect())
+    }
+
+    /// Removes consecutive duplicate elements, like [`Vec::dedup`].
+    pub fn dedup_pipe(mut self) -> Pipe<Vec<T>>
+    where
+        T: PartialEq,
+    {
+        self.0.dedup();
+        self
+    }
+
+    /// Removes consecutive elements that map to the same key, like
+    /// [`Vec::dedup_by_key`].
+    pub fn dedup_by_key<K: PartialEq, F: FnMut(&mut T) -> K>(mut self, key: F) -> Pipe<Vec<T>> {
+        self.0.dedup_by_key(key);
+        self
+    }
+
+    /// Applies `f` to each overlapping `size`-element window of the vector,
+    /// collecting the results. Yields nothing if `size` exceeds the length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    pub fn windows_pipe<B, F: FnMut(&[T]) -> B>(self, size: usize, f: F) -> Pipe<Vec<B>> {
+        Pipe(self.0.windows(size).map(f).collect())
+    }
+
+    /// Reverses the vector in place.
+    pub fn reverse_pipe(mut self) -> Pipe<Vec<T>> {
+        self.0.reverse();
+        self
+    }
+
+    /// Sorts the vector, keeping equal elements in their original order.
+    pub fn sort_pipe(mut self) -> Pipe<Vec<T>>
+    where
+        T: Ord,
+    {
+        self.0.sort();
+        self
+    }
+
+    /// Sorts the vector by the key `f` extracts, keeping elements with equal
+    /// keys in their original order.
+    pub fn sort_by_key_pipe<K: Ord, F: FnMut(&T) -> K>(mut self, f: F) -> Pipe<Vec<T>> {
+        self.0.sort_by_key(f);
+        self
+    }
+
+    /// Groups the elements by the key `f` computes, keeping each group in
+    /// the original order.
+    #[cfg(feature = "std")]
+    pub fn group_by_pipe<K, F>(self, mut key: F) -> Pipe<HashMap<K, Vec<T>>>
+    where
+        K: Eq + Hash,
+        F: FnMut(&T) -> K,
+    {
+        let mut groups: HashMap<K, Vec<T>> = HashMap::new();
+        for item in self.0 {
+            groups.entry(key(&item)).or_default().push(item);
+        }
+        Pipe(groups)
+    }
+
+    /// Alternates elements of the vector with those of `other`, starting
+    /// with the vector and appending the leftovers of the longer source.
+    pub fn interleave_pipe<I: IntoIterator<Item = T>>(self, other: I) -> Pipe<Vec<T>> {
+        let capacity = self.capacity_hint().unwrap_or(self.0.len());
+        let mut mine = self.0.into_iter();
+        let mut theirs = other.into_iter();
+        let mut merged = Vec::with_capacity(capacity);
+        loop {
+            match (mine.next(), theirs.next()) {
+                (None, None) => break,
+                (a, b) => merged.extend(a.into_iter().chain(b)),
+            }
+        }
+        Pipe(merged)
+    }
+
+    /// Maps elements with `f` until it returns `None`, dropping the rest,
+    /// like [`Iterator::map_while`].
+    pub fn map_while_pipe<U, F: FnMut(T) -> Option<U>>(self, f: F) -> Pipe<Vec<U>> {
+        let Some(hint) = self.capacity_hint() else {
+            return Pipe(self.0.into_iter().map_while(f).collect());
+        };
+        let mut mapped = Vec::with_capacity(hint);
+        mapped.extend(self.0.into_iter().map_while(f));
+        Pipe(mapped)
+    }
+
+    /// Repeats the vector `times` times; zero times gives an empty vector.
+    pub fn cycle_pipe(self, times: usize) -> Pipe<Vec<T>>
+    where
+        T: Clone,
+    {
+        let repeated = (0..times).flat_map(|_| self.0.iter().cloned()).collect();
+        Pipe(repeated)
+    }
+
+    /// Splits the vector into the elements matching `pred` and the rest,
+    /// keeping their order.
+    pub fn partition_pipe<F: FnMut(&T) -> bool>(self, pred: F) -> Pipe<(Vec<T>, Vec<T>)> {
+        Pipe(self.0.into_iter().partition(pred))
+    }
+
+    /// Keeps the first `n` elements, or all of them if there are fewer.
+    pub fn take_pipe(mut self, n: usize) -> Pipe<Vec<T>> {
+        self.0.truncate(n);
+        self
+    }
+
+    /// Drops the first `n` elements, leaving an empty vector if there are
+    /// fewer.
+    pub fn skip_pipe(mut self, n: usize) -> Pipe<Vec<T>> {
+        self.0.drain(..n.min(self.0.len()));
+        self
+    }
+
+    /// Like [`chunked`](Pipe::chunked), but processes the chunks in parallel
+    /// on the rayon thread pool. Results keep the order of the chunks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    #[cfg(feature = "rayon")]
+    pub fn par_chunks<B, F>(self, size: usize, f: F) -> Pipe<Vec<B>>
+    where
+        T: Sync,
+        B: Send,
+        F: Fn(&[T]) -> B + Sync,
+    {
+        use rayon::prelude::*;
+
+        Pipe(self.0.par_chunks(size).map(&f).collect())
+    }
+
+    /// Folds the elements into an accumulator starting at `init`, passing
+    /// each element's index to `f` along with it.
+    pub fn fold_indexed<A, F: FnMut(A, usize, T) -> A>(self, init: A, mut f: F) -> Pipe<A> {
+        let folded = self
+            .0
+            .into_iter()
+            .enumerate()
+            .fold(init, |acc, (i, item)| f(acc, i, item));
+        Pipe(folded)
+    }
+
+    /// Reduces the elements with a fallible `f`, stopping at the first
+    /// error. An empty vector reduces to `Ok` with `None`.
+    pub fn try_reduce_pipe<E, F: FnMut(T, T) -> Result<T, E>>(
+        self,
+        mut f: F,
+    ) -> Result<Pipe<Option<T>>, E> {
+        let mut items = self.0.into_iter();
+        let Some(first) = items.next() else {
+            return Ok(Pipe(None));
+        };
+        items.try_fold(first, &mut f).map(|acc| Pipe(Some(acc)))
+    }
+
+    /// Runs `f` against each element in order and passes the vector on
+    /// unchanged, the per-element form of [`tap`](Pipe::tap).
+    pub fn tap_each<F: FnMut(&T)>(self, f: F) -> Pipe<Vec<T>> {
+        self.0.iter().for_each(f);
+        self
+    }
+}
+
+impl<T> Pipe<Vec<Vec<T>>> {
+    /// Concatenates the inner vectors in order.
+    pub fn flatten_pipe(self) -> Pipe<Vec<T>> {
+        Pipe(self.0.into_iter().flatten().collect())
+    }
+}
+
+impl<T> Pipe<Option<T>> {
+    /// Turns `Some` into a one-element vector and `None` into an empty one.
+    pub fn flatten_pipe(self) -> Pipe<Vec<T>> {
+        Pipe(self.0.into_iter().collect())
+    }
+}
+
+impl<L, R> Pipe<Either<L, R>> {
+    /// Ends the chain, turning a `Left` into `Ok` and a `Right` into `Err`.
+    pub fn into_result_pipe(self) -> Result<L, R> {
+        self.0.into()
+    }
+}
+
+/// Borrows the inner value, so its methods can be called on the pipe
+/// directly. Use [`Pipe::into_inner`] to take it out.
+impl<T> Deref for Pipe<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Pipe<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// A [`Pipe`]-like wrapper that records a label and the `Debug` form of the
+/// value after each step.
+///
+/// ```
+/// use aurars::pipe::DebugPipe;
+///
+/// let p = DebugPipe::new(3).pipe_labeled("square", |x| x * x);
+/// assert_eq!(p.trace(), [("square".to_string(), "9".to_string())]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugPipe<T> {
+    value: T,
+    trace: Vec<(String, String)>,
+}
+
+impl<T> DebugPipe<T> {
+    /// Wraps a value with an empty trace.
+    pub fn new(value: T) -> Self {
+        DebugPipe {
+            value,
+            trace: Vec::new(),
+        }
+    }
+
+    /// Applies `f` to the wrapped value and records `label` with the
+    /// result's `Debug` form.
+    pub fn pipe_labeled<B: Debug, F: FnOnce(T) -> B>(self, label: &str, f: F) -> DebugPipe<B> {
+        let value = f(self.value);
+        let mut trace = self.trace;
+        trace.push((label.into(), format!("{value:?}")));
+        DebugPipe { value, trace }
+    }
+
+    /// Returns the recorded `(label, value)` pairs, oldest first.
+    pub fn trace(&self) -> &[(String, String)] {
+        &self.trace
+    }
+
+    /// Consumes the wrapper, returning the value and dropping the trace.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+/// Method-call piping on any value, without the [`Pipe`] wrapper.
+///
+/// ```
+/// use aurars::pipe::PipeExt;
+///
+/// let shout = String::from("aura")
+///     .pipe(|s| s.to_uppercase())
+///     .pipe(|s| s + "!");
+/// assert_eq!(shout, "AURA!");
+/// ```
+pub trait PipeExt: Sized {
+    /// Applies `f` to `self` and returns its result.
+    fn pipe<B, F: FnOnce(Self) -> B>(self, f: F) -> B {
+        f(self)
+    }
+}
+
+impl<T> PipeExt for T {}
+
+/// Pipes a value through a chain of functions, left to right.
+///
+/// Starting from `Pipe::new(x)`, each `=>` step expands as follows:
+///
+/// - `=> f` becomes `.pipe(f)`, for any closure or function expression.
+/// - `=> tap f` becomes `.tap(f)`, so `f` sees a borrow and the value passes
+///   through unchanged.
+/// - `=> .method(args)` calls the method on the piped value, like
+///   `.pipe(|v| v.method(args))`.
+/// - `=> path(args)` calls the function with the piped value inserted as the
+///   first argument, like `.pipe(|v| path(v, args))`.
+///
+/// A call step is always read as a partial call, so to pipe into a function
+/// that `make(arg)` returns, bind it first or write `=> (make(arg))`.
+///
+/// ```
+/// use aurars::pipe;
+///
+/// let result = pipe!(2 => |x| x + 1 => tap |x| assert_eq!(*x, 3) => |x| x * 10);
+/// assert_eq!(result.into_inner(), 30);
+///
+/// let label = pipe!(7u32 => u32::pow(2) => .to_string() => |s| s + "!");
+/// assert_eq!(label.into_inner(), "49!");
+/// ```
+#[macro_export]
+macro_rules! pipe {
+    (@steps $acc:expr;) => {
+        $acc
+    };
+    (@steps $acc:expr; => tap $f:expr $(=> $($rest:tt)+)?) => {
+        $crate::pipe!(@steps $acc.tap($f); $(=> $($rest)+)?)
+    };
+    (@steps $acc:expr;
+        => . $method:ident $(::<$($generic:ty),+>)? ($($arg:expr),* $(,)?)
+        $(=> $($rest:tt)+)?
+    ) => {
+        $crate::pipe!(
+            @steps $acc.pipe(|value| value.$method$(::<$($generic),+>)?($($arg),*));
+            $(=> $($rest)+)?
+        )
+    };
+    (@steps $acc:expr;
+        => $($path:ident)::+ ($($arg:expr),* $(,)?)
+        $(=> $($rest:tt)+)?
+    ) => {
+        $crate::pipe!(
+            @steps $acc.pipe(|value| $($path)::+(value $(, $arg)*));
+            $(=> $($rest)+)?
+        )
+    };
+    (@steps $acc:expr; => $f:expr $(=> $($rest:tt)+)?) => {
+        $crate::pipe!(@steps $acc.pipe($f); $(=> $($rest)+)?)
+    };
+    ($value:expr $(=> $($steps:tt)+)?) => {
+        $crate::pipe!(@steps $crate::pipe::Pipe::new($value); $(=> $($steps)+)?)
+    };
+}
+
+#[doc(hidden)]
+pub fn __trace_start() -> Vec<String> {
+    Vec::new()
+}
+
+#[doc(hidden)]
+pub fn __trace_step<T: Debug>(trace: &mut Vec<String>, value: &T) {
+    trace.push(format!("{value:?}"));
+}
+
+/// Pipes a value through a chain like [`pipe!`](crate::pipe!), also
+/// recording the `Debug` form of the value after each step.
+///
+/// Steps use the same syntax as [`pipe!`](crate::pipe!), and every
+/// intermediate value must be `Debug`. Returns the final value, unwrapped,
+/// with the recorded trace.
+///
+/// ```
+/// use aurars::pipe_trace;
+///
+/// let (len, trace) = pipe_trace!("aura" => str::len => |n| n * 2);
+/// assert_eq!(len, 8);
+/// assert_eq!(trace, ["4", "8"]);
+/// ```
+#[macro_export]
+macro_rules! pipe_trace {
+    (@steps $trace:ident $acc:expr;) => {
+        $acc
+    };
+    (@steps $trace:ident $acc:expr; => tap $f:expr $(=> $($rest:tt)+)?) => {
+        $crate::pipe_trace!(
+            @steps $trace $crate::pipe_trace!(@record $trace $crate::pipe!(@steps $acc; => tap $f));
+            $(=> $($rest)+)?
+        )
+    };
+    (@steps $trace:ident $acc:expr;
+        => . $method:ident $(::<$($generic:ty),+>)? ($($arg:expr),* $(,)?)
+        $(=> $($rest:tt)+)?
+    ) => {
+        $crate::pipe_trace!(
+            @steps $trace $crate::pipe_trace!(
+                @record $trace
+                $crate::pipe!(@steps $acc; => . $method$(::<$($generic),+>)?($($arg),*))
+            );
+            $(=> $($rest)+)?
+        )
+    };
+    (@steps $trace:ident $acc:expr;
+        => $($path:ident)::+ ($($arg:expr),* $(,)?)
+        $(=> $($rest:tt)+)?
+    ) => {
+        $crate::pipe_trace!(
+            @steps $trace $crate::pipe_trace!(
+                @record $trace
+                $crate::pipe!(@steps $acc; => $($path)::+($($arg),*))
+            );
+            $(=> $($rest)+)?
+        )
+    };
+    (@steps $trace:ident $acc:expr; => $f:expr $(=> $($rest:tt)+)?) => {
+        $crate::pipe_trace!(
+            @steps $trace $crate::pipe_trace!(@record $trace $crate::pipe!(@steps $acc; => $f));
+            $(=> $($rest)+)?
+        )
+    };
+    (@record $trace:ident $acc:expr) => {
+        $acc.tap(|value| $crate::pipe::__trace_step(&mut $trace, value))
+    };
+    ($value:expr $(=> $($steps:tt)+)?) => {{
+        // Without steps nothing is recorded, so `trace` is never mutated.
+        #[allow(unused_mut)]
+        let mut trace = $crate::pipe::__trace_start();
+        let value = $crate::pipe_trace!(
+            @steps trace $crate::pipe::Pipe::new($value); $(=> $($steps)+)?
+        )
+        .into_inner();
+        (value, trace)
+    }};
+}
+
+/// Pipes a value through asynchronous steps, left to right.
+///
+/// Each step is a function returning a future, applied with
+/// [`Pipe::pipe_async`] and awaited before the next one starts. The macro
+/// produces a future of the final [`Pipe`], so it runs on any executor.
+///
+/// ```
+/// use aurars::async_pipe;
+///
+/// async fn double(n: i32) -> i32 {
+///     n * 2
+/// }
+///
+/// let chain = async_pipe!(3 => double => |n| async move { n + 1 });
+/// assert_eq!(futures::executor::block_on(chain).into_inner(), 7);
+/// ```
+#[macro_export]
+macro_rules! async_pipe {
+    ($value:expr $(=> $f:expr)*) => {
+        async move {
+            let pipe = $crate::pipe::Pipe::new($value);
+            $(let pipe = pipe.pipe_async($f).await;)*
+            pipe
+        }
+    };
+}
+
+/// Pipes a value through fallible steps, stopping at the first `Err`.
+///
+/// Each step returns a `Result`; an `Ok` flows into the next step and the
+/// first `Err` becomes the result of the whole chain. All steps must share
+/// one error type. Each step goes through [`Pipe::try_map`], and the final
+/// value comes out unwrapped as `Ok`.
+///
+/// ```
+/// use aurars::try_pipe;
+///
+/// let parsed: Result<i32, std::num::ParseIntError> =
+///     try_pipe!("21" => str::parse::<i32> => |n| Ok(n * 2));
+/// assert_eq!(parsed, Ok(42));
+/// ```
+#[macro_export]
+macro_rules! try_pipe {
+    ($value:expr $(=> $f:expr)*) => {
+        'chain: {
+            let pipe = $crate::pipe::Pipe::new($value);
+            $(
+                let pipe = match pipe.try_map($f) {
+                    ::core::result::Result::Ok(pipe) => pipe,
+                    ::core::result::Result::Err(e) => break 'chain ::core::result::Result::Err(e),
+                };
+            )*
+            ::core::result::Result::Ok(pipe.into_inner())
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipe_chain() {
+        let result = pipe!("aura" => str::len => |n| n * 2);
+        assert_eq!(result.into_inner(), 8);
+    }
+
+    #[test]
+    fn try_tap_passes_through() {
+        let result: Result<_, &str> = Pipe::new(4)
+            .try_tap(|x| if *x > 0 { Ok(()) } else { Err("negative") })
+            .map(|p| p.pipe(|x| x * 2));
+        assert_eq!(result.unwrap().into_inner(), 8);
+    }
+
+    #[test]
+    fn try_tap_short_circuits() {
+        let validate = |x: &i32| if *x > 0 { Ok(()) } else { Err("negative") };
+        let run = || -> Result<i32, &str> {
+            let p = Pipe::new(-3).try_tap(validate)?.pipe(|x| x * 2);
+            Ok(p.into_inner())
+        };
+        assert_eq!(run(), Err("negative"));
+    }
+
+    #[test]
+    fn enumerate_pipe_indexes_items() {
+        let indexed = Pipe::new(vec!["a", "b", "c"]).enumerate_pipe().into_inner();
+        assert_eq!(indexed, vec![(0, "a"), (1, "b"), (2, "c")]);
+    }
+
+    #[test]
+    fn flat_map_pipe_expands_ranges() {
+        let expanded = Pipe::new(vec![1, 2, 3])
+            .flat_map_pipe(|n| 0..n)
+            .into_inner();
+        assert_eq!(expanded, vec![0, 0, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn reduce_pipe_finds_max() {
+        let max = Pipe::new(vec![3, 9, 4]).reduce_pipe(i32::max).into_inner();
+        assert_eq!(max, Some(9));
+    }
+
+    #[test]
+    fn reduce_pipe_empty_is_none() {
+        let max = Pipe::new(Vec::<i32>::new())
+            .reduce_pipe(i32::max)
+            .into_inner();
+        assert_eq!(max, None);
+    }
+
+    #[test]
+    fn guard_passes() {
+        let result = Pipe::new(5).guard(|x| *x > 0, "non-positive");
+        assert_eq!(result.map(Pipe::into_inner), Ok(5));
+    }
+
+    #[test]
+    fn guard_fails() {
+        let result = Pipe::new(-5).guard(|x| *x > 0, "non-positive");
+        assert_eq!(result, Err("non-positive"));
+    }
+
+    #[test]
+    fn fan_out_computes_metrics() {
+        let data = Pipe::new(vec![4, 1, 7]);
+        let sum = |v: &Vec<i32>| v.iter().sum::<i32>();
+        let min = |v: &Vec<i32>| *v.iter().min().unwrap();
+        let max = |v: &Vec<i32>| *v.iter().max().unwrap();
+        assert_eq!(data.fan_out(&[&sum, &min, &max]), vec![12, 1, 7]);
+        assert_eq!(data.pipe(|v| v.len()).into_inner(), 3);
+    }
+
+    #[test]
+    fn require_wraps_ok() {
+        let result = Pipe::new(3)
+            .require(|x| *x < 10, "too big")
+            .pipe(|r| r.map(|x| x + 1));
+        assert_eq!(result.into_inner(), Ok(4));
+    }
+
+    #[test]
+    fn require_wraps_err() {
+        let result = Pipe::new(30).require(|x| *x < 10, "too big");
+        assert_eq!(result.into_inner(), Err("too big"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn timed_passes_value_and_reports() {
+        let mut out = Vec::new();
+        let result = Pipe::new(21).timed_to(&mut out, "double", |x| x * 2);
+        assert_eq!(result.into_inner(), 42);
+        let line = String::from_utf8(out).unwrap();
+        assert!(line.starts_with("double: "));
+        assert!(line.ends_with('\n'));
+
+        assert_eq!(Pipe::new(1).timed("inc", |x| x + 1).into_inner(), 2);
+    }
+
+    #[test]
+    fn chunked_processes_short_tail() {
+        let sums = Pipe::new((1..=10).collect::<Vec<i32>>())
+            .chunked(3, |chunk| chunk.iter().sum::<i32>())
+            .into_inner();
+        assert_eq!(sums, vec![6, 15, 24, 10]);
+    }
+
+    #[test]
+    fn dedup_pipe_collapses_runs() {
+        let deduped = Pipe::new(vec![1, 1, 2, 3, 3, 3, 1])
+            .dedup_pipe()
+            .into_inner();
+        assert_eq!(deduped, vec![1, 2, 3, 1]);
+    }
+
+    #[test]
+    fn dedup_by_key_collapses_runs() {
+        let deduped = Pipe::new(vec![10, 11, 20, 25, 31])
+            .dedup_by_key(|n| *n / 10)
+            .into_inner();
+        assert_eq!(deduped, vec![10, 20, 31]);
+    }
+
+    #[test]
+    fn into_result_pipe_both_sides() {
+        let ok = Pipe::new(4)
+            .pipe(Either::<i32, &str>::Left)
+            .into_result_pipe();
+        assert_eq!(ok, Ok(4));
+        let err = Pipe::new("bad")
+            .pipe(Either::<i32, &str>::Right)
+            .into_result_pipe();
+        assert_eq!(err, Err("bad"));
+    }
+
+    #[test]
+    fn try_map_chains_with_question_mark() {
+        fn parse_and_halve(input: &str) -> Result<i32, String> {
+            let value = Pipe::new(input)
+                .try_map(|s| s.parse::<i32>().map_err(|e| e.to_string()))?
+                .try_map(|n| {
+                    if n % 2 == 0 {
+                        Ok(n / 2)
+                    } else {
+                        Err(format!("{} is odd", n))
+                    }
+                })?;
+            Ok(value.into_inner())
+        }
+        assert_eq!(parse_and_halve("42"), Ok(21));
+        assert_eq!(parse_and_halve("7"), Err("7 is odd".to_string()));
+        assert!(parse_and_halve("x").is_err());
+    }
+
+    #[test]
+    fn branch_map_diverges_in_type() {
+        let describe =
+            |n: i32| Pipe::new(n).branch_map(|n| *n > 0, |n| format!("+{}", n), |n| n.abs());
+        assert_eq!(describe(3), Either::Left(Pipe::new("+3".to_string())));
+        assert_eq!(describe(-4), Either::Right(Pipe::new(4)));
+    }
+
+    #[test]
+    fn windows_pipe_pairwise_sums() {
+        let sums = Pipe::new(vec![1, 2, 3, 4])
+            .windows_pipe(2, |w| w[0] + w[1])
+            .into_inner();
+        assert_eq!(sums, vec![3, 5, 7]);
+    }
+
+    #[test]
+    fn windows_pipe_oversized_window_is_empty() {
+        let sums = Pipe::new(vec![1, 2])
+            .windows_pipe(3, |w| w.len())
+            .into_inner();
+        assert!(sums.is_empty());
+    }
+
+    #[test]
+    fn collect_into_extends_existing() {
+        let mut target = vec![1, 2];
+        Pipe::new(vec![3, 4])
+            .pipe(|v| v.into_iter().map(|n| n * 10))
+            .collect_into(&mut target);
+        assert_eq!(target, vec![1, 2, 30, 40]);
+    }
+
+    #[test]
+    fn reverse_pipe_reverses() {
+        assert_eq!(
+            Pipe::new(vec![1, 2, 3]).reverse_pipe().into_inner(),
+            vec![3, 2, 1]
+        );
+    }
+
+    #[test]
+    fn sort_pipe_sorts() {
+        assert_eq!(
+            Pipe::new(vec![3, 1, 2]).sort_pipe().into_inner(),
+            vec![1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn sort_by_key_pipe_is_stable() {
+        let sorted = Pipe::new(vec!["ccc", "a", "bb", "b"])
+            .sort_by_key_pipe(|s| s.len())
+            .into_inner();
+        assert_eq!(sorted, vec!["a", "b", "bb", "ccc"]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn group_by_pipe_by_parity() {
+        let groups = Pipe::new(vec![1, 2, 3, 4, 5])
+            .group_by_pipe(|n| n % 2 == 0)
+            .into_inner();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&true], vec![2, 4]);
+        assert_eq!(groups[&false], vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn interleave_pipe_appends_leftovers() {
+        let merged = Pipe::new(vec![1, 3, 5, 7])
+            .interleave_pipe(vec![2, 4])
+            .into_inner();
+        assert_eq!(merged, vec![1, 2, 3, 4, 5, 7]);
+        let merged = Pipe::new(vec![1])
+            .interleave_pipe(vec![2, 4, 6])
+            .into_inner();
+        assert_eq!(merged, vec![1, 2, 4, 6]);
+    }
+
+    #[test]
+    fn map_while_pipe_stops_at_none() {
+        let parsed = Pipe::new(vec!["1", "2", "x", "4"])
+            .map_while_pipe(|s| s.parse::<i32>().ok())
+            .into_inner();
+        assert_eq!(parsed, vec![1, 2]);
+    }
+
+    #[test]
+    fn cycle_pipe_repeats() {
+        let repeated = Pipe::new(vec![1, 2, 3]).cycle_pipe(2).into_inner();
+        assert_eq!(repeated, vec![1, 2, 3, 1, 2, 3]);
+        assert!(Pipe::new(vec![1, 2, 3])
+            .cycle_pipe(0)
+            .into_inner()
+            .is_empty());
+    }
+
+    #[test]
+    fn partition_pipe_splits_evens_and_odds() {
+        let (evens, odds) = Pipe::new(vec![1, 2, 3, 4, 5])
+            .partition_pipe(|n| n % 2 == 0)
+            .into_inner();
+        assert_eq!(evens, vec![2, 4]);
+        assert_eq!(odds, vec![1, 3, 5]);
+    }
+
+    #[test]
+    fn pipe_ext_chains_on_plain_values() {
+        assert_eq!(5.pipe(|x| x + 1).pipe(|x| x * 2), 12);
+    }
+
+    #[test]
+    fn pipe_ext_does_not_shadow_pipe_method() {
+        let wrapped = Pipe::new(3).pipe(|x| x * 3);
+        assert_eq!(wrapped, Pipe::new(9));
+    }
+
+    #[test]
+    fn take_and_skip_pipe() {
+        assert_eq!(
+            Pipe::new(vec![1, 2, 3]).take_pipe(2).into_inner(),
+            vec![1, 2]
+        );
+        assert_eq!(Pipe::new(vec![1, 2, 3]).skip_pipe(2).into_inner(), vec![3]);
+    }
+
+    #[test]
+    fn take_and_skip_pipe_beyond_length() {
+        assert_eq!(
+            Pipe::new(vec![1, 2, 3]).take_pipe(10).into_inner(),
+            vec![1, 2, 3]
+        );
+        assert!(Pipe::new(vec![1, 2, 3])
+            .skip_pipe(10)
+            .into_inner()
+            .is_empty());
+    }
+
+    #[test]
+    fn tap_leaves_value_untouched() {
+        let seen = std::cell::Cell::new(0);
+        let result = Pipe::new(4).tap(|x| seen.set(*x)).pipe(|x| x + 1);
+        assert_eq!(seen.get(), 4);
+        assert_eq!(result.into_inner(), 5);
+    }
+
+    #[test]
+    fn pipe_macro_tap_steps() {
+        let seen = std::cell::Cell::new((0, 0));
+        let result = pipe!(1
+            => |x| x * 2
+            => tap |x| seen.set((*x, seen.get().1))
+            => |x| x + 10
+            => tap |x| seen.set((seen.get().0, *x)));
+        assert_eq!(seen.get(), (2, 12));
+        assert_eq!(result.into_inner(), 12);
+    }
+
+    #[test]
+    fn pipe_macro_without_steps() {
+        assert_eq!(pipe!(7).into_inner(), 7);
+    }
+
+    fn parse_positive(s: &str) -> Result<u32, String> {
+        s.parse().map_err(|_| format!("not a number: {s}"))
+    }
+
+    fn nonzero(n: u32) -> Result<u32, String> {
+        if n == 0 {
+            Err("zero".to_string())
+        } else {
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn try_pipe_succeeds_end_to_end() {
+        let result = try_pipe!("12" => parse_positive => nonzero => |n| Ok(n + 1));
+        assert_eq!(result, Ok(13));
+    }
+
+    #[test]
+    fn try_pipe_stops_at_second_step() {
+        let mut ran_third = false;
+        let result = try_pipe!("0" => parse_positive => nonzero => |n| {
+            ran_third = true;
+            Ok(n)
+        });
+        assert_eq!(result, Err("zero".to_string()));
+        assert!(!ran_third);
+    }
+
+    #[test]
+    fn pipe_mut_then_len() {
+        let len = Pipe::new(vec![3, 1, 2])
+            .pipe_mut(|v| v.push(4))
+            .pipe_mut(|v| v.retain(|&n| n > 1))
+            .pipe(|v| v.len());
+        assert_eq!(len.into_inner(), 3);
+    }
+
+    #[test]
+    fn pipe_ref_does_not_consume() {
+        let pipe = Pipe::new(vec![1, 2, 3]);
+        assert_eq!(pipe.pipe_ref(|v| v.iter().sum::<i32>()), 6);
+        assert_eq!(pipe.into_inner(), vec![1, 2, 3]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn memo_pipe_runs_stage_once_per_input() {
+        let cache = std::cell::RefCell::new(HashMap::new());
+        let runs = std::cell::Cell::new(0);
+        let square = |n: &u64| {
+            runs.set(runs.get() + 1);
+            n * n
+        };
+        for _ in 0..3 {
+            assert_eq!(Pipe::new(9).memo_pipe(&cache, square).into_inner(), 81);
+        }
+        assert_eq!(Pipe::new(4).memo_pipe(&cache, square).into_inner(), 16);
+        assert_eq!(runs.get(), 2);
+        assert_eq!(cache.borrow().len(), 2);
+    }
+
+    fn scale(x: i32, factor: i32) -> i32 {
+        x * factor
+    }
+
+    fn clamp_to(x: i32, lo: i32, hi: i32) -> i32 {
+        x.clamp(lo, hi)
+    }
+
+    #[test]
+    fn pipe_macro_mixes_step_kinds() {
+        let result = pipe!(" 12 "
+            => .trim()
+            => str::parse::<i32>
+            => .unwrap()
+            => scale(3)
+            => clamp_to(0, 30)
+            => |x| x + 1
+            => .to_string());
+        assert_eq!(result.into_inner(), "31");
+    }
+
+    #[test]
+    fn pipe_macro_method_with_turbofish() {
+        let doubled = pipe!(vec![1, 2, 3]
+            => .into_iter()
+            => .map(|x| x * 2)
+            => .collect::<Vec<_>>());
+        assert_eq!(doubled.into_inner(), vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn pipe_macro_parenthesized_call_is_applied() {
+        let add = |n: i32| move |x: i32| x + n;
+        assert_eq!(pipe!(1 => (add(4))).into_inner(), 5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn inspect_passes_value_through() {
+        let v = Pipe::new(vec![1, 2])
+            .inspect()
+            .inspect_labeled("stage")
+            .into_inner();
+        assert_eq!(v, vec![1, 2]);
+    }
+
+    #[test]
+    fn scan_with_state_cumulative_sums() {
+        let (sums, total) = Pipe::new(vec![1, 2, 3, 4]).scan_with_state(0, |acc, x| {
+            *acc += x;
+            *acc
+        });
+        assert_eq!(sums.into_inner(), vec![1, 3, 6, 10]);
+        assert_eq!(total, 10);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_chunks_matches_sequential() {
+        let data: Vec<u64> = (1..=1000).collect();
+        let sum = |chunk: &[u64]| chunk.iter().sum::<u64>();
+        let parallel = Pipe::new(data.clone()).par_chunks(64, sum);
+        let sequential = Pipe::new(data).chunked(64, sum);
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn fold_indexed_weighted_sum() {
+        let weighted = Pipe::new(vec![3, 1, 4, 1])
+            .fold_indexed(0, |acc, i, x| acc + (i + 1) * x)
+            .into_inner();
+        assert_eq!(weighted, 3 + 2 + 12 + 4);
+    }
+
+    #[test]
+    fn try_reduce_pipe_success_and_empty() {
+        let add = |a: u8, b: u8| a.checked_add(b).ok_or("overflow");
+        assert_eq!(
+            Pipe::new(vec![1u8, 2, 3]).try_reduce_pipe(add),
+            Ok(Pipe::new(Some(6)))
+        );
+        assert_eq!(
+            Pipe::new(Vec::<u8>::new()).try_reduce_pipe(add),
+            Ok(Pipe::new(None))
+        );
+    }
+
+    #[test]
+    fn try_reduce_pipe_stops_at_error() {
+        let mut calls = 0;
+        let add = |a: u8, b: u8| {
+            calls += 1;
+            a.checked_add(b).ok_or("overflow")
+        };
+        let result = Pipe::new(vec![200u8, 50, 10, 1]).try_reduce_pipe(add);
+        assert_eq!(result, Err("overflow"));
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn deref_reaches_inner_methods() {
+        let mut pipe = Pipe::new(String::from("aura"));
+        assert_eq!(pipe.len(), 4);
+        pipe.push_str("rs");
+        assert_eq!(*pipe, "aurars");
+        assert_eq!(pipe.into_inner(), "aurars");
+    }
+
+    async fn add_one(n: u32) -> u32 {
+        n + 1
+    }
+
+    #[test]
+    fn async_pipe_awaits_steps_in_order() {
+        let result = futures::executor::block_on(async_pipe!(1
+            => add_one
+            => |n| async move { n * 10 }));
+        assert_eq!(result.into_inner(), 20);
+    }
+
+    #[test]
+    fn pipe_async_chains_with_await() {
+        let result = futures::executor::block_on(async {
+            Pipe::new(5)
+                .pipe_async(add_one)
+                .await
+                .pipe_async(add_one)
+                .await
+        });
+        assert_eq!(result, Pipe::new(7));
+    }
+
+    #[test]
+    fn with_capacity_does_not_change_results() {
+        let run = |hint: Option<usize>| {
+            let pipe = Pipe::new(vec![5, 3, 5, 1]);
+            let pipe = match hint {
+                Some(n) => pipe.with_capacity(n),
+                None => pipe,
+            };
+            pipe.sort_pipe().dedup_pipe().interleave_pipe(vec![9, 8])
+        };
+        let hinted = run(Some(64));
+        assert_eq!(hinted, run(None));
+        assert_eq!(*hinted, vec![1, 9, 3, 8, 5]);
+        assert!(hinted.capacity() >= 64);
+
+        let mapped = Pipe::new(vec![1, 2, -1, 3])
+            .with_capacity(32)
+            .map_while_pipe(|n| (n > 0).then_some(n * 2));
+        assert_eq!(*mapped, vec![2, 4]);
+        assert!(mapped.capacity() >= 32);
+    }
+
+    #[test]
+    fn map_while_pipe_without_hint_allocates_for_output() {
+        let items: Vec<i32> = (0..1000).collect();
+        assert_eq!(items.capacity(), items.len());
+        let mapped = Pipe::new(items).map_while_pipe(|n| (n < 1).then_some(n as u64));
+        assert_eq!(*mapped, vec![0]);
+        assert!(mapped.capacity() < 1000);
+    }
+
+    #[test]
+    fn flatten_pipe_nested_vectors() {
+        let flat = Pipe::new(vec![vec![1, 2], vec![], vec![3]])
+            .flatten_pipe()
+            .reverse_pipe();
+        assert_eq!(flat.into_inner(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn flatten_pipe_options() {
+        assert_eq!(Pipe::new(Some('a')).flatten_pipe().into_inner(), vec!['a']);
+        assert!(Pipe::new(None::<char>)
+            .flatten_pipe()
+            .into_inner()
+            .is_empty());
+    }
+
+    #[test]
+    fn tap_each_counts_elements() {
+        let mut seen = 0;
+        let doubled = Pipe::new(vec![1, 2, 3])
+            .tap_each(|_| seen += 1)
+            .pipe(|v| v.into_iter().map(|x| x * 2).collect::<Vec<_>>());
+        assert_eq!(seen, 3);
+        assert_eq!(doubled.into_inner(), vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn pipe_trace_records_each_step() {
+        let (value, trace) = pipe_trace!(1.5f64 => |x| x * 2.0 => f64::powi(2) => |x| x - 1.0);
+        assert_eq!(value, 8.0);
+        assert_eq!(trace, ["3.0", "9.0", "8.0"]);
+    }
+
+    #[test]
+    fn pipe_trace_without_steps_is_empty() {
+        let (value, trace) = pipe_trace!(5);
+        assert_eq!(value, 5);
+        assert!(trace.is_empty());
+    }
+
+    #[test]
+    fn debug_pipe_records_labeled_stages() {
+        let p = DebugPipe::new("4")
+            .pipe_labeled("parse", |s| s.parse::<i32>().unwrap())
+            .pipe_labeled("double", |n| n * 2)
+            .pipe_labeled("describe", |n| format!("n={n}"));
+        let labels: Vec<&str> = p.trace().iter().map(|(l, _)| l.as_str()).collect();
+        let values: Vec<&str> = p.trace().iter().map(|(_, v)| v.as_str()).collect();
+        assert_eq!(labels, ["parse", "double", "describe"]);
+        assert_eq!(values, ["4", "8", "\"n=8\""]);
+        assert_eq!(p.into_inner(), "n=8");
+    }
+
+    #[test]
+    fn pipe_recur_loops_from_piped_value() {
+        // Collatz steps from 27 down to 1.
+        let steps = pipe!(3u32 => |n| (n * 9, 0)).recur(|(n, steps): (u32, u32)| {
+            if n == 1 {
+                ControlFlow::Break(steps)
+            } else if n.is_multiple_of(2) {
+                ControlFlow::Continue((n / 2, steps + 1))
+            } else {
+                ControlFlow::Continue((3 * n + 1, steps + 1))
+            }
+        });
+        assert_eq!(steps, 111);
+    }
+
+    #[test]
+    fn pipe_trace_handles_long_chains() {
+        let (value, trace) = pipe_trace!(
+            vec![3u64, 1, 4, 1, 5]
+                => |v: Vec<u64>| v.into_iter().map(|n| n * 2 + 1).collect::<Vec<u64>>()
+                => |v: Vec<u64>| v.into_iter().filter(|n| n % 3 != 0).collect::<Vec<u64>>()
+                => tap |v: &Vec<u64>| assert!(v.iter().all(|n| n % 2 == 1), "odd {v:?}")
+                => .into_iter()
+                => |it: alloc::vec::IntoIter<u64>| it.rev().map(|n| n + 1).collect::<Vec<u64>>()
+                => |v: Vec<u64>| v.iter().copied().fold(0u64, |acc, n| acc * 10 + n % 10)
+                => u64::pow(2)
+                => |n: u64| if n > 1_000_000 { n / 1_000 } else { n * 1_000 }
+        );
+        assert_eq!(value, 784_000);
+        assert_eq!(
+            trace,
+            [
+                "[7, 3, 9, 3, 11]",
+                "[7, 11]",
+                "[7, 11]",
+                "IntoIter([7, 11])",
+                "[12, 8]",
+                "28",
+                "784",
+                "784000",
+            ]
+        );
+    }
+}
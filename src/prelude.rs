@@ -0,0 +1,56 @@
+//! Common names in one import.
+//!
+//! `use aurars::prelude::*;` brings in:
+//!
+//! - [`Either`] and its variants [`Left`] and [`Right`]
+//! - [`Pipe`] and the [`pipe!`](crate::pipe!) macro
+//! - [`recur`]
+//! - the [`try_left!`](crate::try_left!), [`try_right!`](crate::try_right!),
+//!   [`recur_break!`](crate::recur_break!) and
+//!   [`recur_continue!`](crate::recur_continue!) macros
+
+pub use crate::either::Either::{self, Left, Right};
+pub use crate::pipe::Pipe;
+pub use crate::recur::recur;
+pub use crate::{pipe, recur_break, recur_continue, try_left, try_right};
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    fn halve_evens(n: u32) -> Either<u32, &'static str> {
+        if n.is_multiple_of(2) {
+            Left(n / 2)
+        } else {
+            Right("odd")
+        }
+    }
+
+    fn quarter(n: u32) -> Either<u32, &'static str> {
+        let half = try_left!(halve_evens(n));
+        halve_evens(half)
+    }
+
+    fn odd_message(n: u32) -> Either<u32, &'static str> {
+        let message = try_right!(halve_evens(n));
+        Right(message)
+    }
+
+    #[test]
+    fn prelude_covers_each_module() {
+        assert_eq!(quarter(12), Left(3));
+        assert_eq!(odd_message(3), Right("odd"));
+
+        let total = recur((0, 1), |(sum, n)| {
+            if n > 4 {
+                recur_break!(sum)
+            } else {
+                recur_continue!((sum + n, n + 1))
+            }
+        });
+        assert_eq!(total, 10);
+
+        let piped: Pipe<usize> = pipe!("aura" => str::len);
+        assert_eq!(piped, Pipe::new(4));
+    }
+}
@@ -0,0 +1,874 @@
+//! Loops expressed as repeated application of a step function.
+//!
+//! A step receives the current state and returns `ControlFlow::Continue`
+//! with the next state or `ControlFlow::Break` with the final result.
+
+use core::future::Future;
+use core::ops::ControlFlow;
+#[cfg(feature = "std")]
+use std::collections::hash_map::DefaultHasher;
+#[cfg(feature = "std")]
+use std::hash::{Hash, Hasher};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::either::Either::{self, Left, Right};
+
+pub mod coroutine;
+#[cfg(feature = "std")]
+pub mod graph;
+pub mod numeric;
+pub mod sim;
+pub mod stream;
+#[cfg(feature = "std")]
+pub mod timing;
+
+/// Repeatedly applies `f` to the state, starting from `initial`, until it
+/// breaks with a value.
+///
+/// ```
+/// use aurars::recur::recur;
+/// use core::ops::ControlFlow;
+///
+/// let total = recur((0, 1), |(sum, n)| {
+///     if n > 10 {
+///         ControlFlow::Break(sum)
+///     } else {
+///         ControlFlow::Continue((sum + n, n + 1))
+///     }
+/// });
+/// assert_eq!(total, 55);
+/// ```
+pub fn recur<C, B, F>(initial: C, mut f: F) -> B
+where
+    F: FnMut(C) -> ControlFlow<B, C>,
+{
+    let mut state = initial;
+    loop {
+        match f(state) {
+            ControlFlow::Continue(next) => state = next,
+            ControlFlow::Break(result) => return result,
+        }
+    }
+}
+
+/// Runs two `recur`-style loops side by side, advancing `f` then `g` by one
+/// step per round until both have broken.
+///
+/// Once a loop breaks it stops advancing and its result is held until the
+/// other loop breaks too.
+pub fn interleave<C1, C2, B1, B2, F, G>(s1: C1, s2: C2, f: F, g: G) -> (B1, B2)
+where
+    F: Fn(C1) -> ControlFlow<B1, C1>,
+    G: Fn(C2) -> ControlFlow<B2, C2>,
+{
+    let start: (Either<C1, B1>, Either<C2, B2>) = (Left(s1), Left(s2));
+    recur(start, |(first, second)| {
+        let first = match first {
+            Left(state) => f(state).into(),
+            done => done,
+        };
+        let second = match second {
+            Left(state) => g(state).into(),
+            done => done,
+        };
+        match (first, second) {
+            (Right(b1), Right(b2)) => ControlFlow::Break((b1, b2)),
+            states => ControlFlow::Continue(states),
+        }
+    })
+}
+
+/// Steps from `initial` with `f` until it returns `None`, recording every
+/// state visited, including the initial and the final one.
+pub fn trace_states<S: Clone, F: Fn(S) -> Option<S>>(initial: S, f: F) -> Vec<S> {
+    recur((initial, Vec::new()), |(state, mut trace)| {
+        trace.push(state.clone());
+        match f(state) {
+            Some(next) => ControlFlow::Continue((next, trace)),
+            None => ControlFlow::Break(trace),
+        }
+    })
+}
+
+/// Like [`recur`], but the step returns an [`Either`]: `Left` continues
+/// with the next state and `Right` breaks with the result.
+pub fn recur_either<C, B, F>(initial: C, f: F) -> B
+where
+    F: Fn(C) -> Either<C, B>,
+{
+    recur(initial, |state| f(state).into())
+}
+
+/// Like [`recur`], but each step first pays `cost(&state)` out of `fuel`.
+///
+/// Returns `Err(state)` with the unstepped state once a step costs more than
+/// the fuel left.
+pub fn recur_fuel<C, B, F>(initial: C, fuel: u64, cost: impl Fn(&C) -> u64, f: F) -> Result<B, C>
+where
+    F: Fn(C) -> ControlFlow<B, C>,
+{
+    recur((initial, fuel), |(state, fuel)| {
+        let Some(fuel) = fuel.checked_sub(cost(&state)) else {
+            return ControlFlow::Break(Err(state));
+        };
+        match f(state) {
+            ControlFlow::Continue(next) => ControlFlow::Continue((next, fuel)),
+            ControlFlow::Break(result) => ControlFlow::Break(Ok(result)),
+        }
+    })
+}
+
+/// Like [`recur`], but gives up after `max_steps` calls to `f`.
+///
+/// Returns `Err(state)` with the last continue value if `f` has not broken
+/// by then; with `max_steps` of 0, `f` is never called.
+pub fn recur_bounded<C, B, F>(initial: C, max_steps: usize, mut f: F) -> Result<B, C>
+where
+    F: FnMut(C) -> ControlFlow<B, C>,
+{
+    recur((initial, 0), |(state, step)| {
+        if step >= max_steps {
+            return ControlFlow::Break(Err(state));
+        }
+        match f(state) {
+            ControlFlow::Continue(next) => ControlFlow::Continue((next, step + 1)),
+            ControlFlow::Break(result) => ControlFlow::Break(Ok(result)),
+        }
+    })
+}
+
+/// Like [`recur`], but checks `should_cancel` before each call to `f` and
+/// stops once it returns `true`.
+///
+/// Returns `Err(state)` with the state `f` would have received next when
+/// cancelled, so a shutdown flag or deadline can interrupt a long loop
+/// without losing its progress.
+pub fn recur_cancellable<C, B, F, K>(initial: C, should_cancel: K, mut f: F) -> Result<B, C>
+where
+    F: FnMut(C) -> ControlFlow<B, C>,
+    K: Fn() -> bool,
+{
+    recur(initial, |state| {
+        if should_cancel() {
+            return ControlFlow::Break(Err(state));
+        }
+        f(state).map_break(Ok)
+    })
+}
+
+/// Like [`recur`], but `f` also receives the zero-based index of the
+/// current iteration, which advances on each continue.
+pub fn recur_indexed<C, B, F>(initial: C, mut f: F) -> B
+where
+    F: FnMut(usize, C) -> ControlFlow<B, C>,
+{
+    recur((0, initial), |(index, state)| match f(index, state) {
+        ControlFlow::Continue(next) => ControlFlow::Continue((index + 1, next)),
+        ControlFlow::Break(result) => ControlFlow::Break(result),
+    })
+}
+
+/// Like [`recur`], but the step can fail: the first `Err` ends the loop
+/// and is returned, and a break is returned as `Ok`.
+pub fn recur_try<C, B, E, F>(initial: C, f: F) -> Result<B, E>
+where
+    F: Fn(C) -> Result<ControlFlow<B, C>, E>,
+{
+    recur(initial, |state| match f(state) {
+        Ok(ControlFlow::Continue(next)) => ControlFlow::Continue(next),
+        Ok(ControlFlow::Break(result)) => ControlFlow::Break(Ok(result)),
+        Err(e) => ControlFlow::Break(Err(e)),
+    })
+}
+
+/// Like [`recur`], but threads an accumulator `acc` alongside the state.
+///
+/// `f` takes the state and the accumulator and either continues with both
+/// or breaks with a result and the final accumulator, which are returned
+/// together.
+pub fn recur_fold<C, A, B, F>(initial: C, acc: A, f: F) -> (B, A)
+where
+    F: Fn(C, A) -> ControlFlow<(B, A), (C, A)>,
+{
+    recur((initial, acc), |(state, acc)| f(state, acc))
+}
+
+/// Applies `f` to the state for as long as `pred` holds, returning the
+/// first state for which it does not. `pred` is checked before each step.
+pub fn recur_while<C, F, P>(initial: C, pred: P, f: F) -> C
+where
+    F: Fn(C) -> C,
+    P: Fn(&C) -> bool,
+{
+    recur(initial, |state| {
+        if pred(&state) {
+            ControlFlow::Continue(f(state))
+        } else {
+            ControlFlow::Break(state)
+        }
+    })
+}
+
+/// Applies `f` to the state until `pred` holds, returning the first state
+/// for which it does. `pred` is checked before each step.
+pub fn recur_until<C, F, P>(initial: C, pred: P, f: F) -> C
+where
+    F: Fn(C) -> C,
+    P: Fn(&C) -> bool,
+{
+    recur_while(initial, |state| !pred(state), f)
+}
+
+/// Applies `f` until two successive states hash the same, or `max` steps
+/// have run, returning the last state.
+///
+/// Comparing hashes avoids an expensive `PartialEq` on large states, at the
+/// cost of a small chance of stopping early on a hash collision.
+#[cfg(feature = "std")]
+pub fn recur_until_hash<C: Hash, F: Fn(C) -> C>(initial: C, max: usize, f: F) -> C {
+    fn hash_of<C: Hash>(value: &C) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    let hash = hash_of(&initial);
+    recur((initial, hash, 0), |(state, hash, step)| {
+        if step >= max {
+            return ControlFlow::Break(state);
+        }
+        let next = f(state);
+        let next_hash = hash_of(&next);
+        if next_hash == hash {
+            ControlFlow::Break(next)
+        } else {
+            ControlFlow::Continue((next, next_hash, step + 1))
+        }
+    })
+}
+
+/// Steps from `initial` with `step` until it returns `None`, returning the
+/// last state.
+///
+/// `on_change` is called with each new state that differs from the one
+/// before it; repeats of the previous state are skipped. The initial state
+/// is not reported.
+pub fn run_distinct<C, F>(initial: C, step: impl Fn(C) -> Option<C>, on_change: F) -> C
+where
+    C: PartialEq + Clone,
+    F: FnMut(&C),
+{
+    recur((initial, on_change), |(state, mut on_change)| {
+        match step(state.clone()) {
+            None => ControlFlow::Break(state),
+            Some(next) => {
+                if next != state {
+                    on_change(&next);
+                }
+                ControlFlow::Continue((next, on_change))
+            }
+        }
+    })
+}
+
+/// Sweeps two indices over `slice`, starting with `lo` at the first element
+/// and `hi` at the last.
+///
+/// `f` is only called while `lo < hi`, so both indices are always in bounds
+/// and distinct. It returns the accumulator with the next `(lo, hi)` to
+/// continue, or breaks with a final accumulator. The sweep also ends, with
+/// the current accumulator, once the indices meet or cross. Each continue
+/// should move at least one index toward the other, or the sweep never ends.
+pub fn two_pointer<T, A, F>(slice: &[T], init: A, f: F) -> A
+where
+    F: Fn(A, usize, usize) -> ControlFlow<A, (A, usize, usize)>,
+{
+    let hi = slice.len().saturating_sub(1);
+    recur((init, 0, hi), |(acc, lo, hi)| {
+        if lo >= hi {
+            ControlFlow::Break(acc)
+        } else {
+            f(acc, lo, hi)
+        }
+    })
+}
+
+/// Like [`recur`], but each step is asynchronous: the future `f` returns is
+/// awaited before deciding whether to continue.
+///
+/// This only relies on [`Future`], so it runs on any executor.
+pub async fn recur_async<C, B, F, Fut>(initial: C, f: F) -> B
+where
+    F: Fn(C) -> Fut,
+    Fut: Future<Output = ControlFlow<B, C>>,
+{
+    let mut state = initial;
+    loop {
+        match f(state).await {
+            ControlFlow::Continue(next) => state = next,
+            ControlFlow::Break(result) => return result,
+        }
+    }
+}
+
+/// A computation that is either finished or has another step to take,
+/// for writing deep or mutual recursion without growing the stack.
+pub enum Trampoline<T> {
+    /// The final value.
+    Done(T),
+    /// The next step, called by [`run`].
+    More(Box<dyn FnOnce() -> Trampoline<T>>),
+}
+
+/// Calls the steps of `t` one after another until it is
+/// [`Done`](Trampoline::Done), returning the value.
+pub fn run<T>(t: Trampoline<T>) -> T {
+    recur(t, |t| match t {
+        Trampoline::Done(value) => ControlFlow::Break(value),
+        Trampoline::More(next) => ControlFlow::Continue(next()),
+    })
+}
+
+/// Marks a [`Recur`] with no step limit.
+#[derive(Debug, Clone, Copy)]
+pub struct Unbounded;
+
+/// Marks a [`Recur`] limited by [`max_steps`](Recur::max_steps).
+#[derive(Debug, Clone, Copy)]
+pub struct Bounded(usize);
+
+/// Builder for the `recur` family, for when positional arguments get hard
+/// to read.
+///
+/// `Recur::new(initial)` runs like [`recur`]. Adding
+/// [`max_steps`](Recur::max_steps) runs like [`recur_bounded`] and makes
+/// `run` return a `Result`; [`indexed`](Recur::indexed) passes the
+/// iteration index to the step, like [`recur_indexed`].
+///
+/// ```
+/// use aurars::recur::Recur;
+/// use std::ops::ControlFlow;
+///
+/// let steps = Recur::new(1u64).max_steps(100).indexed().run(|i, n| {
+///     if n >= 1000 {
+///         ControlFlow::Break(i)
+///     } else {
+///         ControlFlow::Continue(n * 3)
+///     }
+/// });
+/// assert_eq!(steps, Ok(7));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Recur<C, Limit = Unbounded> {
+    initial: C,
+    limit: Limit,
+}
+
+/// A [`Recur`] whose step also receives the iteration index.
+#[derive(Debug, Clone, Copy)]
+pub struct RecurIndexed<C, Limit = Unbounded> {
+    initial: C,
+    limit: Limit,
+}
+
+impl<C> Recur<C> {
+    /// Starts a loop from `initial`.
+    pub fn new(initial: C) -> Self {
+        Recur {
+            initial,
+            limit: Unbounded,
+        }
+    }
+}
+
+impl<C, Limit> Recur<C, Limit> {
+    /// Gives up after `n` steps, as in [`recur_bounded`].
+    pub fn max_steps(self, n: usize) -> Recur<C, Bounded> {
+        Recur {
+            initial: self.initial,
+            limit: Bounded(n),
+        }
+    }
+
+    /// Passes the zero-based iteration index to the step.
+    pub fn indexed(self) -> RecurIndexed<C, Limit> {
+        RecurIndexed {
+            initial: self.initial,
+            limit: self.limit,
+        }
+    }
+}
+
+impl<C> Recur<C, Unbounded> {
+    /// Runs the loop with [`recur`].
+    pub fn run<B, F: FnMut(C) -> ControlFlow<B, C>>(self, f: F) -> B {
+        recur(self.initial, f)
+    }
+}
+
+impl<C> Recur<C, Bounded> {
+    /// Runs the loop with [`recur_bounded`].
+    pub fn run<B, F: FnMut(C) -> ControlFlow<B, C>>(self, f: F) -> Result<B, C> {
+        recur_bounded(self.initial, self.limit.0, f)
+    }
+}
+
+impl<C, Limit> RecurIndexed<C, Limit> {
+    /// Gives up after `n` steps, as in [`recur_bounded`].
+    pub fn max_steps(self, n: usize) -> RecurIndexed<C, Bounded> {
+        RecurIndexed {
+            initial: self.initial,
+            limit: Bounded(n),
+        }
+    }
+}
+
+impl<C> RecurIndexed<C, Unbounded> {
+    /// Runs the loop with [`recur_indexed`].
+    pub fn run<B, F: FnMut(usize, C) -> ControlFlow<B, C>>(self, f: F) -> B {
+        recur_indexed(self.initial, f)
+    }
+}
+
+impl<C> RecurIndexed<C, Bounded> {
+    /// Runs the loop with [`recur_bounded`], passing the iteration index to
+    /// `f`.
+    pub fn run<B, F>(self, mut f: F) -> Result<B, C>
+    where
+        F: FnMut(usize, C) -> ControlFlow<B, C>,
+    {
+        let step = |(index, state)| match f(index, state) {
+            ControlFlow::Continue(next) => ControlFlow::Continue((index + 1, next)),
+            ControlFlow::Break(result) => ControlFlow::Break(result),
+        };
+        recur_bounded((0, self.initial), self.limit.0, step).map_err(|(_, state)| state)
+    }
+}
+
+/// Iterator extension for folds that can stop early.
+pub trait FoldWhileExt: Iterator + Sized {
+    /// Folds the items into an accumulator until `f` breaks or the iterator
+    /// is exhausted, returning the accumulator either way.
+    fn fold_while<A, F>(self, init: A, f: F) -> A
+    where
+        F: FnMut(A, Self::Item) -> ControlFlow<A, A>,
+    {
+        recur((self, init, f), |(mut iter, acc, mut f)| {
+            match iter.next() {
+                None => ControlFlow::Break(acc),
+                Some(item) => match f(acc, item) {
+                    ControlFlow::Continue(acc) => ControlFlow::Continue((iter, acc, f)),
+                    ControlFlow::Break(acc) => ControlFlow::Break(acc),
+                },
+            }
+        })
+    }
+}
+
+impl<I: Iterator> FoldWhileExt for I {}
+
+/// Ends a `recur` step with the given result.
+#[macro_export]
+macro_rules! recur_break {
+    ($value:expr) => {
+        ::core::ops::ControlFlow::Break($value)
+    };
+}
+
+/// Continues a `recur` step with the given state.
+#[macro_export]
+macro_rules! recur_continue {
+    ($value:expr) => {
+        ::core::ops::ControlFlow::Continue($value)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recur_counts() {
+        let result = recur(0, |n| {
+            if n == 10 {
+                recur_break!(n * 2)
+            } else {
+                recur_continue!(n + 1)
+            }
+        });
+        assert_eq!(result, 20);
+    }
+
+    #[test]
+    fn fold_while_stops_past_threshold() {
+        let sum = (1..).fold_while(0, |acc, n| {
+            let acc = acc + n;
+            if acc > 20 {
+                ControlFlow::Break(acc)
+            } else {
+                ControlFlow::Continue(acc)
+            }
+        });
+        assert_eq!(sum, 21);
+    }
+
+    #[test]
+    fn fold_while_exhausts_iterator() {
+        let sum = [1, 2, 3]
+            .into_iter()
+            .fold_while(0, |acc, n| ControlFlow::Continue(acc + n));
+        assert_eq!(sum, 6);
+    }
+
+    #[test]
+    fn interleave_counters_of_different_lengths() {
+        let log = std::cell::RefCell::new(Vec::new());
+        let count_to = |name: &'static str, limit: u32| {
+            let log = &log;
+            move |n: u32| {
+                log.borrow_mut().push((name, n));
+                if n == limit {
+                    ControlFlow::Break(n * 10)
+                } else {
+                    ControlFlow::Continue(n + 1)
+                }
+            }
+        };
+        let result = interleave(0, 0, count_to("a", 1), count_to("b", 3));
+        assert_eq!(result, (10, 30));
+        assert_eq!(
+            log.into_inner(),
+            vec![("a", 0), ("b", 0), ("a", 1), ("b", 1), ("b", 2), ("b", 3)]
+        );
+    }
+
+    #[test]
+    fn trace_states_records_trajectory() {
+        let trace = trace_states(3u32, |n| n.checked_sub(1));
+        assert_eq!(trace, vec![3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn recur_either_counts() {
+        let result = recur_either(0, |n| if n == 10 { Right(n * 2) } else { Left(n + 1) });
+        assert_eq!(result, 20);
+    }
+
+    #[test]
+    fn recur_fuel_cheap_loop_completes() {
+        let count = |n: u32| {
+            if n == 10 {
+                ControlFlow::Break(n)
+            } else {
+                ControlFlow::Continue(n + 1)
+            }
+        };
+        assert_eq!(recur_fuel(0, 100, |_| 1, count), Ok(10));
+    }
+
+    #[test]
+    fn recur_fuel_expensive_loop_runs_out() {
+        let count = |n: u32| {
+            if n == 10 {
+                ControlFlow::Break(n)
+            } else {
+                ControlFlow::Continue(n + 1)
+            }
+        };
+        assert_eq!(recur_fuel(0, 100, |_| 25, count), Err(4));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn recur_until_hash_converges() {
+        let settled = recur_until_hash(vec![9, 4, 7, 1], 100, |mut v| {
+            if let Some(i) = (1..v.len()).find(|&i| v[i - 1] > v[i]) {
+                v.swap(i - 1, i);
+            }
+            v
+        });
+        assert_eq!(settled, vec![1, 4, 7, 9]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn recur_until_hash_respects_max() {
+        assert_eq!(recur_until_hash(0u64, 5, |n| n + 1), 5);
+    }
+
+    #[test]
+    fn run_distinct_skips_repeats() {
+        let readings = [1, 1, 2, 2, 2, 3, 1, 1];
+        let position = std::cell::Cell::new(0);
+        let next_reading = |_| {
+            position.set(position.get() + 1);
+            readings.get(position.get()).copied()
+        };
+        let mut changes = Vec::new();
+        let last = run_distinct(readings[0], next_reading, |&r| changes.push(r));
+        assert_eq!(last, 1);
+        assert_eq!(changes, vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn two_pointer_finds_pair_sum() {
+        let sorted = [1, 3, 4, 6, 8, 11];
+        let find = |target: i32| {
+            two_pointer(&sorted, None, |acc, lo, hi| {
+                let sum = sorted[lo] + sorted[hi];
+                if sum == target {
+                    ControlFlow::Break(Some((lo, hi)))
+                } else if sum < target {
+                    ControlFlow::Continue((acc, lo + 1, hi))
+                } else {
+                    ControlFlow::Continue((acc, lo, hi - 1))
+                }
+            })
+        };
+        assert_eq!(find(10), Some((2, 3)));
+        assert_eq!(find(100), None);
+        assert_eq!(
+            two_pointer(&[] as &[i32], 0, |acc, _, _| ControlFlow::Break(acc + 1)),
+            0
+        );
+    }
+
+    #[test]
+    fn recur_async_counts_to_ten() {
+        let count = recur_async(0, |n| async move {
+            if n == 10 {
+                ControlFlow::Break(n)
+            } else {
+                ControlFlow::Continue(n + 1)
+            }
+        });
+        assert_eq!(futures::executor::block_on(count), 10);
+    }
+
+    #[test]
+    fn recur_bounded_converges() {
+        let halve = |n: u32| {
+            if n == 0 {
+                ControlFlow::Break("done")
+            } else {
+                ControlFlow::Continue(n / 2)
+            }
+        };
+        assert_eq!(recur_bounded(100, 10, halve), Ok("done"));
+    }
+
+    #[test]
+    fn recur_bounded_runs_out_of_steps() {
+        let count_up = |n: u32| ControlFlow::<(), u32>::Continue(n + 1);
+        assert_eq!(recur_bounded(0, 5, count_up), Err(5));
+        assert_eq!(recur_bounded(7, 0, count_up), Err(7));
+    }
+
+    #[test]
+    fn recur_indexed_counts_iterations() {
+        let stopped_at = recur_indexed("ignored", |i, state| {
+            if i == 5 {
+                ControlFlow::Break(i)
+            } else {
+                ControlFlow::Continue(state)
+            }
+        });
+        assert_eq!(stopped_at, 5);
+    }
+
+    #[test]
+    fn recur_accepts_fn_mut() {
+        let mut log: Vec<i32> = Vec::new();
+        let last = recur(1, |n| {
+            log.push(n);
+            if n >= 8 {
+                ControlFlow::Break(n)
+            } else {
+                ControlFlow::Continue(n * 2)
+            }
+        });
+        assert_eq!(last, 8);
+        assert_eq!(log, vec![1, 2, 4, 8]);
+    }
+
+    fn checked_countdown(n: i32) -> Result<ControlFlow<&'static str, i32>, String> {
+        match n {
+            0 => Ok(ControlFlow::Break("liftoff")),
+            n if n < 0 => Err(format!("negative count {n}")),
+            n => Ok(ControlFlow::Continue(n - 1)),
+        }
+    }
+
+    #[test]
+    fn recur_try_succeeds() {
+        assert_eq!(recur_try(3, checked_countdown), Ok("liftoff"));
+    }
+
+    #[test]
+    fn recur_try_breaks_with_value() {
+        let sum = recur_try((0, 1), |(sum, n)| {
+            Ok::<_, ()>(if n > 4 {
+                ControlFlow::Break(sum)
+            } else {
+                ControlFlow::Continue((sum + n, n + 1))
+            })
+        });
+        assert_eq!(sum, Ok(10));
+    }
+
+    #[test]
+    fn recur_try_stops_on_error() {
+        assert_eq!(
+            recur_try(-2, checked_countdown),
+            Err("negative count -2".to_string())
+        );
+    }
+
+    fn is_even(n: u64) -> Trampoline<bool> {
+        if n == 0 {
+            Trampoline::Done(true)
+        } else {
+            Trampoline::More(Box::new(move || is_odd(n - 1)))
+        }
+    }
+
+    fn is_odd(n: u64) -> Trampoline<bool> {
+        if n == 0 {
+            Trampoline::Done(false)
+        } else {
+            Trampoline::More(Box::new(move || is_even(n - 1)))
+        }
+    }
+
+    fn factorial_mod(n: u64, acc: u64) -> Trampoline<u64> {
+        const MODULUS: u64 = 1_000_000_007;
+        if n == 0 {
+            Trampoline::Done(acc)
+        } else {
+            Trampoline::More(Box::new(move || factorial_mod(n - 1, acc * n % MODULUS)))
+        }
+    }
+
+    #[test]
+    fn run_handles_deep_mutual_recursion() {
+        assert!(run(is_even(1_000_000)));
+        assert!(run(is_odd(999_999)));
+    }
+
+    #[test]
+    fn run_factorial() {
+        assert_eq!(run(factorial_mod(10, 1)), 3_628_800);
+        assert_eq!(run(factorial_mod(1_000_000, 1)), 641_102_369);
+    }
+
+    #[test]
+    fn recur_fold_sums_with_accumulator() {
+        let (reached_end, sum) = recur_fold(1, 0, |n, sum| {
+            if n > 10 {
+                ControlFlow::Break((true, sum))
+            } else {
+                ControlFlow::Continue((n + 1, sum + n))
+            }
+        });
+        assert!(reached_end);
+        assert_eq!(sum, 55);
+    }
+
+    #[test]
+    fn recur_while_counts_to_threshold() {
+        assert_eq!(recur_while(0, |n| *n < 10, |n| n + 3), 12);
+        assert_eq!(recur_while(50, |n| *n < 10, |n| n + 3), 50);
+    }
+
+    #[test]
+    fn recur_until_counts_to_threshold() {
+        assert_eq!(recur_until(1, |n| *n >= 100, |n| n * 2), 128);
+        assert_eq!(recur_until(1, |n| *n >= 1, |n| n * 2), 1);
+    }
+
+    #[test]
+    fn recur_builder_unbounded() {
+        let total = Recur::new((0, 1)).run(|(sum, n)| {
+            if n > 10 {
+                ControlFlow::Break(sum)
+            } else {
+                ControlFlow::Continue((sum + n, n + 1))
+            }
+        });
+        assert_eq!(total, 55);
+    }
+
+    #[test]
+    fn recur_builder_bounded_indexed() {
+        let count_up = |i: usize, n: u32| {
+            if n >= 3 {
+                ControlFlow::Break(i)
+            } else {
+                ControlFlow::Continue(n + 1)
+            }
+        };
+        assert_eq!(Recur::new(0).max_steps(10).indexed().run(count_up), Ok(3));
+        assert_eq!(Recur::new(0).indexed().max_steps(2).run(count_up), Err(2));
+        assert_eq!(Recur::new(0).indexed().run(count_up), 3);
+    }
+
+    #[test]
+    fn recur_cancellable_returns_partial_state() {
+        let checks = core::cell::Cell::new(0);
+        let cancel = || {
+            checks.set(checks.get() + 1);
+            checks.get() > 3
+        };
+        let result: Result<(), u32> =
+            recur_cancellable(0, cancel, |n| ControlFlow::Continue(n + 1));
+        assert_eq!(result, Err(3));
+        assert_eq!(checks.get(), 4);
+    }
+
+    #[test]
+    fn recur_cancellable_finishes_when_not_cancelled() {
+        let result: Result<u32, u32> = recur_cancellable(
+            0,
+            || false,
+            |n| {
+                if n == 5 {
+                    ControlFlow::Break(n * 2)
+                } else {
+                    ControlFlow::Continue(n + 1)
+                }
+            },
+        );
+        assert_eq!(result, Ok(10));
+    }
+
+    #[test]
+    fn recur_builder_accepts_mutating_steps() {
+        let mut seen = Vec::new();
+        let result: Result<(), u32> = Recur::new(0).max_steps(3).run(|n| {
+            seen.push(n);
+            ControlFlow::Continue(n + 1)
+        });
+        assert_eq!(result, Err(3));
+        assert_eq!(seen, [0, 1, 2]);
+
+        let mut calls = 0;
+        let last = Recur::new(10).indexed().run(|i, n| {
+            calls += 1;
+            if i == 2 {
+                ControlFlow::Break(n)
+            } else {
+                ControlFlow::Continue(n * 2)
+            }
+        });
+        assert_eq!((last, calls), (40, 3));
+
+        let mut calls = 0;
+        let bounded: Result<(), u32> = Recur::new(0).indexed().max_steps(4).run(|_, n| {
+            calls += 1;
+            ControlFlow::Continue(n + 1)
+        });
+        assert_eq!((bounded, calls), (Err(4), 4));
+    }
+}
@@ -0,0 +1,78 @@
+//! Resumable computations that hand control back to the caller.
+
+use core::ops::ControlFlow;
+
+use alloc::boxed::Box;
+
+/// A computation that yields `Y` values to its caller one at a time and
+/// finally returns an `R`.
+///
+/// Each [`resume`](Coroutine::resume) runs the step function once. A
+/// `Continue(y)` yields `y` and leaves the coroutine ready to resume again;
+/// a `Break(r)` returns `r` and completes it.
+pub struct Coroutine<Y, R> {
+    step: Option<Box<dyn FnMut() -> ControlFlow<R, Y>>>,
+}
+
+impl<Y, R> Coroutine<Y, R> {
+    /// Creates a coroutine driven by `step`.
+    pub fn new(step: impl FnMut() -> ControlFlow<R, Y> + 'static) -> Self {
+        Coroutine {
+            step: Some(Box::new(step)),
+        }
+    }
+
+    /// Runs the coroutine until it yields or returns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the coroutine has already returned.
+    pub fn resume(&mut self) -> ControlFlow<R, Y> {
+        let step = self
+            .step
+            .as_mut()
+            .expect("coroutine resumed after returning");
+        let flow = step();
+        if flow.is_break() {
+            self.step = None;
+        }
+        flow
+    }
+
+    /// Returns `true` once the coroutine has returned.
+    pub fn is_done(&self) -> bool {
+        self.step.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coroutine_yields_then_returns() {
+        let mut n = 0;
+        let mut co = Coroutine::new(move || {
+            n += 1;
+            if n <= 3 {
+                ControlFlow::Continue(n * 10)
+            } else {
+                ControlFlow::Break("done")
+            }
+        });
+        assert_eq!(co.resume(), ControlFlow::Continue(10));
+        assert_eq!(co.resume(), ControlFlow::Continue(20));
+        assert_eq!(co.resume(), ControlFlow::Continue(30));
+        assert!(!co.is_done());
+        assert_eq!(co.resume(), ControlFlow::Break("done"));
+        assert!(co.is_done());
+    }
+
+    #[test]
+    #[should_panic(expected = "resumed after returning")]
+    fn coroutine_panics_when_resumed_after_return() {
+        let mut co: Coroutine<(), ()> = Coroutine::new(|| ControlFlow::Break(()));
+        let _ = co.resume();
+        let _ = co.resume();
+    }
+}
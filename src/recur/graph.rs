@@ -0,0 +1,262 @@
+//! Graph traversals built on [`recur`].
+
+use core::ops::ControlFlow;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::hash::Hash;
+
+use super::recur;
+
+/// Walks the graph reachable from `start` depth-first, returning the nodes
+/// in pre-order.
+///
+/// Neighbors are visited in the order `neighbors` returns them, and each
+/// node is visited once even if the graph has cycles.
+pub fn dfs<N, F>(start: N, neighbors: F) -> Vec<N>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> Vec<N>,
+{
+    let state = (vec![start], HashSet::new(), Vec::new(), neighbors);
+    recur(
+        state,
+        |(mut stack, mut visited, mut order, mut neighbors)| {
+            let Some(node) = stack.pop() else {
+                return ControlFlow::Break(order);
+            };
+            if visited.insert(node.clone()) {
+                stack.extend(neighbors(&node).into_iter().rev());
+                order.push(node);
+            }
+            ControlFlow::Continue((stack, visited, order, neighbors))
+        },
+    )
+}
+
+/// Finds a cheapest path from `start` to `goal` with A* search, returning
+/// the nodes along it from `start` to `goal` inclusive.
+///
+/// `neighbors` gives each successor with the cost of the edge to it, and
+/// `heuristic` estimates the remaining cost to `goal`. The path is only
+/// guaranteed to be cheapest if the heuristic never overestimates. Returns
+/// `None` if `goal` is unreachable.
+pub fn astar<N, F, H>(start: N, goal: N, neighbors: F, heuristic: H) -> Option<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    F: Fn(&N) -> Vec<(N, u64)>,
+    H: Fn(&N) -> u64,
+{
+    // `N` need not be `Ord`, so the open set orders indices into `entries`.
+    let open = BinaryHeap::from([(Reverse(heuristic(&start)), 0)]);
+    let entries: Vec<(N, u64)> = vec![(start.clone(), 0)];
+    let best: HashMap<N, u64> = HashMap::from([(start, 0)]);
+    let came_from: HashMap<N, N> = HashMap::new();
+    recur(
+        (open, entries, best, came_from),
+        |(mut open, mut entries, mut best, mut came_from)| {
+            let Some((_, index)) = open.pop() else {
+                return ControlFlow::Break(None);
+            };
+            let (node, cost) = entries[index].clone();
+            if best.get(&node).is_some_and(|&known| known < cost) {
+                // A cheaper route to `node` was queued after this one.
+                return ControlFlow::Continue((open, entries, best, came_from));
+            }
+            if node == goal {
+                let mut path = vec![node];
+                while let Some(prev) = path.last().and_then(|n| came_from.get(n)) {
+                    path.push(prev.clone());
+                }
+                path.reverse();
+                return ControlFlow::Break(Some(path));
+            }
+            for (next, weight) in neighbors(&node) {
+                let next_cost = cost.saturating_add(weight);
+                if best.get(&next).is_none_or(|&known| next_cost < known) {
+                    best.insert(next.clone(), next_cost);
+                    came_from.insert(next.clone(), node.clone());
+                    let estimate = next_cost.saturating_add(heuristic(&next));
+                    open.push((Reverse(estimate), entries.len()));
+                    entries.push((next, next_cost));
+                }
+            }
+            ControlFlow::Continue((open, entries, best, came_from))
+        },
+    )
+}
+
+/// Computes the cheapest distance from `start` to every node reachable
+/// from it, using Dijkstra's algorithm.
+///
+/// `neighbors` gives each successor with the cost of the edge to it.
+/// `start` itself is at distance 0.
+pub fn dijkstra<N, F>(start: N, neighbors: F) -> HashMap<N, u64>
+where
+    N: Eq + Hash + Clone,
+    F: Fn(&N) -> Vec<(N, u64)>,
+{
+    // As in `astar`, the heap orders indices into `entries` since `N` need
+    // not be `Ord`.
+    let open = BinaryHeap::from([Reverse((0, 0))]);
+    let entries = vec![start.clone()];
+    let dist: HashMap<N, u64> = HashMap::from([(start, 0)]);
+    recur(
+        (open, entries, dist),
+        |(mut open, mut entries, mut dist)| {
+            let Some(Reverse((cost, index))) = open.pop() else {
+                return ControlFlow::Break(dist);
+            };
+            let node = entries[index].clone();
+            if dist.get(&node).is_some_and(|&known| known < cost) {
+                return ControlFlow::Continue((open, entries, dist));
+            }
+            for (next, weight) in neighbors(&node) {
+                let next_cost = cost.saturating_add(weight);
+                if dist.get(&next).is_none_or(|&known| next_cost < known) {
+                    dist.insert(next.clone(), next_cost);
+                    open.push(Reverse((next_cost, entries.len())));
+                    entries.push(next);
+                }
+            }
+            ControlFlow::Continue((open, entries, dist))
+        },
+    )
+}
+
+/// Walks the graph reachable from `start` breadth-first, returning the
+/// nodes grouped by their distance from `start`.
+///
+/// The first layer is just `start`. Within a layer, nodes appear in the
+/// order they were discovered, and each node appears only once, in the
+/// layer where it was first reached.
+pub fn bfs_layers<N, F>(start: N, neighbors: F) -> Vec<Vec<N>>
+where
+    N: Eq + Hash + Clone,
+    F: Fn(&N) -> Vec<N>,
+{
+    let visited = HashSet::from([start.clone()]);
+    recur(
+        (vec![start], visited, Vec::new()),
+        |(frontier, mut visited, mut layers)| {
+            if frontier.is_empty() {
+                return ControlFlow::Break(layers);
+            }
+            let next: Vec<N> = frontier
+                .iter()
+                .flat_map(&neighbors)
+                .filter(|n| visited.insert(n.clone()))
+                .collect();
+            layers.push(frontier);
+            ControlFlow::Continue((next, visited, layers))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dfs_pre_order_with_cycle() {
+        let edges = |n: &u32| match n {
+            0 => vec![1, 2],
+            1 => vec![3],
+            2 => vec![0, 3],
+            3 => vec![1],
+            _ => vec![],
+        };
+        assert_eq!(dfs(0, edges), vec![0, 1, 3, 2]);
+    }
+
+    const GRID: [[u64; 3]; 3] = [[1, 1, 1], [9, 9, 1], [1, 1, 1]];
+
+    fn grid_neighbors(&(row, col): &(usize, usize)) -> Vec<((usize, usize), u64)> {
+        let mut out = Vec::new();
+        if row > 0 {
+            out.push((row - 1, col));
+        }
+        if row < 2 {
+            out.push((row + 1, col));
+        }
+        if col > 0 {
+            out.push((row, col - 1));
+        }
+        if col < 2 {
+            out.push((row, col + 1));
+        }
+        out.into_iter().map(|(r, c)| ((r, c), GRID[r][c])).collect()
+    }
+
+    #[test]
+    fn astar_routes_around_expensive_cells() {
+        let goal = (2, 0);
+        let manhattan = |&(r, c): &(usize, usize)| (r.abs_diff(goal.0) + c.abs_diff(goal.1)) as u64;
+        let path = astar((0, 0), goal, grid_neighbors, manhattan);
+        assert_eq!(
+            path,
+            Some(vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (2, 1), (2, 0)])
+        );
+    }
+
+    #[test]
+    fn astar_start_is_goal() {
+        assert_eq!(astar(1, 1, |_| vec![(2, 1)], |_| 0), Some(vec![1]));
+    }
+
+    #[test]
+    fn astar_unreachable_goal() {
+        let edges = |n: &u32| if *n < 3 { vec![(n + 1, 1)] } else { vec![] };
+        assert_eq!(astar(0, 10, edges, |_| 0), None);
+    }
+
+    #[test]
+    fn dijkstra_distances() {
+        let edges = |n: &char| match n {
+            'a' => vec![('b', 7), ('c', 2)],
+            'c' => vec![('b', 3), ('d', 8)],
+            'b' => vec![('d', 1)],
+            'd' => vec![('a', 1)],
+            _ => vec![],
+        };
+        let dist = dijkstra('a', edges);
+        assert_eq!(
+            dist,
+            HashMap::from([('a', 0), ('b', 5), ('c', 2), ('d', 6)])
+        );
+    }
+
+    #[test]
+    fn dijkstra_skips_unreachable() {
+        let dist = dijkstra(0u8, |n| if *n == 0 { vec![(1, 4)] } else { vec![] });
+        assert_eq!(dist.len(), 2);
+        assert!(!dist.contains_key(&2));
+    }
+
+    #[test]
+    fn bfs_layers_on_tree() {
+        //      1
+        //    2   3
+        //   4 5   6
+        let children = |n: &u32| match n {
+            1 => vec![2, 3],
+            2 => vec![4, 5],
+            3 => vec![6],
+            _ => vec![],
+        };
+        assert_eq!(
+            bfs_layers(1, children),
+            vec![vec![1], vec![2, 3], vec![4, 5, 6]]
+        );
+    }
+
+    #[test]
+    fn bfs_layers_lists_each_node_once() {
+        let edges = |n: &u32| match n {
+            0 => vec![1, 2],
+            1 => vec![0, 2, 3],
+            2 => vec![3],
+            _ => vec![0],
+        };
+        assert_eq!(bfs_layers(0, edges), vec![vec![0], vec![1, 2], vec![3]]);
+    }
+}
@@ -0,0 +1,565 @@
+//! Numeric iteration built on [`recur`].
+
+use core::ops::ControlFlow;
+
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::recur;
+
+/// Iterates `x = f(x)` from `x0` until successive values differ by less than
+/// `tol`, returning the last value.
+///
+/// Returns `None` if the values have not converged after `max_iter` steps.
+pub fn iterate_tol<F: Fn(f64) -> f64>(x0: f64, tol: f64, max_iter: usize, f: F) -> Option<f64> {
+    recur((x0, 0), |(x, i)| {
+        if i >= max_iter {
+            return ControlFlow::Break(None);
+        }
+        let next = f(x);
+        if (next - x).abs() < tol {
+            ControlFlow::Break(Some(next))
+        } else {
+            ControlFlow::Continue((next, i + 1))
+        }
+    })
+}
+
+/// Finds a root of `f` with Newton's method, stepping `x -= f(x) / df(x)`
+/// from `x0` until a step is smaller than `tol`.
+///
+/// Returns `None` if that takes more than `max_iter` steps, or if the
+/// derivative is zero or the iterate stops being finite.
+pub fn newton<F, D>(x0: f64, f: F, df: D, tol: f64, max_iter: usize) -> Option<f64>
+where
+    F: Fn(f64) -> f64,
+    D: Fn(f64) -> f64,
+{
+    recur((x0, 0), |(x, i)| {
+        let slope = df(x);
+        if i >= max_iter || slope == 0.0 {
+            return ControlFlow::Break(None);
+        }
+        let step = f(x) / slope;
+        let next = x - step;
+        if !next.is_finite() {
+            ControlFlow::Break(None)
+        } else if step.abs() < tol {
+            ControlFlow::Break(Some(next))
+        } else {
+            ControlFlow::Continue((next, i + 1))
+        }
+    })
+}
+
+/// Maximum number of rounds [`converge_pair`] runs before giving up.
+pub const CONVERGE_PAIR_MAX_ITER: usize = 10_000;
+
+/// Updates `a` and `b` together with `f` until neither changes, returning
+/// the settled pair.
+///
+/// After [`CONVERGE_PAIR_MAX_ITER`] rounds the latest pair is returned even
+/// if it has not settled.
+pub fn converge_pair<A, B, F>(a0: A, b0: B, f: F) -> (A, B)
+where
+    A: PartialEq + Clone,
+    B: PartialEq + Clone,
+    F: Fn(&A, &B) -> (A, B),
+{
+    recur((a0, b0, 0), |(a, b, i)| {
+        if i >= CONVERGE_PAIR_MAX_ITER {
+            return ControlFlow::Break((a, b));
+        }
+        let (next_a, next_b) = f(&a, &b);
+        if next_a == a && next_b == b {
+            ControlFlow::Break((a, b))
+        } else {
+            ControlFlow::Continue((next_a, next_b, i + 1))
+        }
+    })
+}
+
+/// Averages draws from `sample` until the standard error of the running
+/// mean drops below `tolerance` or `max_samples` draws have been taken.
+///
+/// Returns the estimate and the number of samples used. At least two
+/// samples are needed before the error can be judged; with `max_samples`
+/// of 0 nothing is drawn and the estimate is `0.0`.
+#[cfg(feature = "std")]
+pub fn monte_carlo<F: FnMut() -> f64>(
+    sample: F,
+    tolerance: f64,
+    max_samples: usize,
+) -> (f64, usize) {
+    // Welford's online algorithm: running mean and sum of squared deviations.
+    recur((0usize, 0.0, 0.0, sample), |(n, mean, m2, mut sample)| {
+        if n >= max_samples {
+            return ControlFlow::Break((mean, n));
+        }
+        let x = sample();
+        let n = n + 1;
+        let delta = x - mean;
+        let mean = mean + delta / n as f64;
+        let m2 = m2 + delta * (x - mean);
+        if n >= 2 {
+            let variance = m2 / (n - 1) as f64;
+            if (variance / n as f64).sqrt() < tolerance {
+                return ControlFlow::Break((mean, n));
+            }
+        }
+        ControlFlow::Continue((n, mean, m2, sample))
+    })
+}
+
+/// Parameters that can take a step against a gradient.
+pub trait GradientStep {
+    /// Returns `self - rate * gradient`.
+    fn step(&self, gradient: &Self, rate: f64) -> Self;
+}
+
+impl GradientStep for f64 {
+    fn step(&self, gradient: &Self, rate: f64) -> Self {
+        self - rate * gradient
+    }
+}
+
+/// Steps element-wise; `gradient` must have the same length as `self`.
+impl GradientStep for Vec<f64> {
+    fn step(&self, gradient: &Self, rate: f64) -> Self {
+        debug_assert_eq!(self.len(), gradient.len());
+        self.iter()
+            .zip(gradient)
+            .map(|(p, g)| p - rate * g)
+            .collect()
+    }
+}
+
+/// Runs `steps` rounds of gradient descent from `start`, moving against
+/// `grad` scaled by `learning_rate` each round.
+pub fn descend<P, G>(start: P, learning_rate: f64, steps: usize, grad: G) -> P
+where
+    P: GradientStep + Clone,
+    G: Fn(&P) -> P,
+{
+    recur((start, 0), |(params, i)| {
+        if i >= steps {
+            ControlFlow::Break(params)
+        } else {
+            let next = params.step(&grad(&params), learning_rate);
+            ControlFlow::Continue((next, i + 1))
+        }
+    })
+}
+
+/// Minimizes `energy` by simulated annealing for `steps` rounds, returning
+/// the lowest-energy state seen.
+///
+/// Each round proposes `neighbor(&current)` and accepts it with the
+/// Metropolis rule: always if it lowers the energy, otherwise with
+/// probability `exp(-delta / schedule(round))`. `rng` supplies uniform
+/// draws in `[0, 1)`, so any random source can be plugged in.
+#[cfg(feature = "std")]
+pub fn anneal<S, F, N>(
+    initial: S,
+    energy: F,
+    neighbor: N,
+    schedule: impl Fn(usize) -> f64,
+    steps: usize,
+    rng: impl FnMut() -> f64,
+) -> S
+where
+    S: Clone,
+    F: Fn(&S) -> f64,
+    N: FnMut(&S) -> S,
+{
+    let e = energy(&initial);
+    let start = (initial.clone(), e, initial, e, 0, neighbor, rng);
+    recur(
+        start,
+        |(current, current_e, best, best_e, round, mut neighbor, mut rng)| {
+            if round >= steps {
+                return ControlFlow::Break(best);
+            }
+            let candidate = neighbor(&current);
+            let candidate_e = energy(&candidate);
+            let delta = candidate_e - current_e;
+            let accept = delta <= 0.0 || rng() < (-delta / schedule(round)).exp();
+            let (current, current_e) = if accept {
+                (candidate, candidate_e)
+            } else {
+                (current, current_e)
+            };
+            let (best, best_e) = if current_e < best_e {
+                (current.clone(), current_e)
+            } else {
+                (best, best_e)
+            };
+            ControlFlow::Continue((current, current_e, best, best_e, round + 1, neighbor, rng))
+        },
+    )
+}
+
+/// Estimates the dominant eigenvector of the square `matrix` by power
+/// iteration, starting from the all-ones vector.
+///
+/// Repeats `v = normalize(matrix * v)` until no component moves by `tol`
+/// or more, or `iterations` rounds have run, and returns the unit-length
+/// estimate. If the product is ever the zero vector, the last estimate is
+/// returned as is.
+#[cfg(feature = "std")]
+pub fn power_iteration(matrix: &[Vec<f64>], iterations: usize, tol: f64) -> Vec<f64> {
+    fn normalize(v: Vec<f64>) -> Option<Vec<f64>> {
+        let norm = v.iter().map(|x| x * x).sum::<f64>().sqrt();
+        (norm > 0.0).then(|| v.into_iter().map(|x| x / norm).collect())
+    }
+
+    let start = vec![1.0; matrix.len()];
+    let start = normalize(start.clone()).unwrap_or(start);
+    recur((start, 0), |(v, i)| {
+        if i >= iterations {
+            return ControlFlow::Break(v);
+        }
+        let product = matrix
+            .iter()
+            .map(|row| row.iter().zip(&v).map(|(a, x)| a * x).sum())
+            .collect();
+        let Some(next) = normalize(product) else {
+            return ControlFlow::Break(v);
+        };
+        let moved = next.iter().zip(&v).any(|(a, b)| (a - b).abs() >= tol);
+        if moved {
+            ControlFlow::Continue((next, i + 1))
+        } else {
+            ControlFlow::Break(next)
+        }
+    })
+}
+
+/// Clusters `points` into `k` groups with Lloyd's algorithm, returning the
+/// centroids and the index of the centroid each point is assigned to.
+///
+/// The first `k` points seed the centroids. Each round assigns every point
+/// to its nearest centroid, then moves each centroid to the mean of its
+/// points; a centroid left with no points stays where it was. Stops once
+/// the assignments stop changing, or after `max_iter` updates.
+///
+/// # Panics
+///
+/// Panics if `k` is 0 or greater than the number of points.
+pub fn kmeans(points: &[Vec<f64>], k: usize, max_iter: usize) -> (Vec<Vec<f64>>, Vec<usize>) {
+    fn nearest(point: &[f64], centroids: &[Vec<f64>]) -> usize {
+        let dist = |c: &[f64]| -> f64 { point.iter().zip(c).map(|(a, b)| (a - b) * (a - b)).sum() };
+        (0..centroids.len())
+            .min_by(|&a, &b| dist(&centroids[a]).total_cmp(&dist(&centroids[b])))
+            .unwrap_or(0)
+    }
+
+    assert!(
+        k > 0 && k <= points.len(),
+        "kmeans needs between 1 and points.len() clusters"
+    );
+    let dim = points[0].len();
+    let start = (points[..k].to_vec(), Vec::new(), 0);
+    recur(start, |(mut centroids, previous, i)| {
+        let assignments: Vec<usize> = points.iter().map(|p| nearest(p, &centroids)).collect();
+        if assignments == previous || i >= max_iter {
+            return ControlFlow::Break((centroids, assignments));
+        }
+        for (cluster, centroid) in centroids.iter_mut().enumerate() {
+            let mut sum = vec![0.0; dim];
+            let mut count = 0;
+            for (point, _) in points
+                .iter()
+                .zip(&assignments)
+                .filter(|(_, &a)| a == cluster)
+            {
+                sum.iter_mut().zip(point).for_each(|(s, x)| *s += x);
+                count += 1;
+            }
+            if count > 0 {
+                *centroid = sum.into_iter().map(|s| s / count as f64).collect();
+            }
+        }
+        ControlFlow::Continue((centroids, assignments, i + 1))
+    })
+}
+
+/// Fills a `rows` by `cols` table cell by cell in row-major order, setting
+/// each cell to `f(row, col, &table)`, and returns the finished table.
+///
+/// When `f` runs, every earlier cell holds its final value and every later
+/// cell is still 0.
+pub fn fill_table<F>(rows: usize, cols: usize, f: F) -> Vec<Vec<u64>>
+where
+    F: FnMut(usize, usize, &Vec<Vec<u64>>) -> u64,
+{
+    let table = vec![vec![0; cols]; rows];
+    recur((table, 0, f), |(mut table, cell, mut f)| {
+        if cols == 0 || cell >= rows * cols {
+            return ControlFlow::Break(table);
+        }
+        let (row, col) = (cell / cols, cell % cols);
+        table[row][col] = f(row, col, &table);
+        ControlFlow::Continue((table, cell + 1, f))
+    })
+}
+
+/// Returns term `n` of the linear recurrence
+/// `a[n] = coeffs[0] * a[n - 1] + coeffs[1] * a[n - 2] + ...`, whose first
+/// terms are `seeds`.
+///
+/// Runs in `O(n * k)` time for `k` coefficients, keeping only the last `k`
+/// terms. With no coefficients every term is 0. Arithmetic wraps on
+/// overflow in both debug and release builds, so large terms come out
+/// modulo 2^64.
+///
+/// # Panics
+///
+/// Panics if `seeds` and `coeffs` differ in length.
+pub fn linear_recurrence(coeffs: &[i64], seeds: &[i64], n: usize) -> i64 {
+    assert_eq!(
+        seeds.len(),
+        coeffs.len(),
+        "linear_recurrence needs one seed per coefficient"
+    );
+    if seeds.is_empty() {
+        return 0;
+    }
+    let window: VecDeque<i64> = seeds.iter().copied().collect();
+    recur((window, 0), |(mut window, first)| {
+        if n < first + window.len() {
+            return ControlFlow::Break(window[n - first]);
+        }
+        let next = coeffs
+            .iter()
+            .zip(window.iter().rev())
+            .fold(0i64, |sum, (c, a)| sum.wrapping_add(c.wrapping_mul(*a)));
+        window.pop_front();
+        window.push_back(next);
+        ControlFlow::Continue((window, first + 1))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iterate_tol_converges_to_sqrt() {
+        let root = iterate_tol(1.0, 1e-12, 100, |x| (x + 2.0 / x) / 2.0).unwrap();
+        assert!((root - 2f64.sqrt()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn iterate_tol_gives_up() {
+        assert_eq!(iterate_tol(0.0, 1e-6, 10, |x| x + 1.0), None);
+    }
+
+    #[test]
+    fn converge_pair_meets_in_the_middle() {
+        let step = |a: &i32, b: &i32| ((*a + 1).min(*b), (*b - 1).max(*a));
+        assert_eq!(converge_pair(0, 10, step), (5, 5));
+    }
+
+    #[test]
+    fn converge_pair_stops_at_guard() {
+        let (a, b) = converge_pair(0u64, 0u64, |a, b| (a + 1, b + 2));
+        assert_eq!(a as usize, CONVERGE_PAIR_MAX_ITER);
+        assert_eq!(b as usize, 2 * CONVERGE_PAIR_MAX_ITER);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn monte_carlo_constant_sampler_stops_early() {
+        assert_eq!(monte_carlo(|| 4.0, 1e-3, 1000), (4.0, 2));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn monte_carlo_alternating_sampler_converges() {
+        let mut flip = false;
+        let sampler = || {
+            flip = !flip;
+            if flip {
+                1.0
+            } else {
+                3.0
+            }
+        };
+        let (estimate, samples) = monte_carlo(sampler, 0.1, 10_000);
+        assert!((estimate - 2.0).abs() < 0.05);
+        assert!(samples > 2 && samples < 10_000);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn monte_carlo_respects_max_samples() {
+        let mut n = 0.0;
+        let (_, samples) = monte_carlo(
+            || {
+                n += 1.0;
+                n
+            },
+            1e-9,
+            50,
+        );
+        assert_eq!(samples, 50);
+    }
+
+    #[test]
+    fn descend_minimizes_quadratic() {
+        // f(x, y) = (x - 3)^2 + (y + 1)^2
+        let grad = |p: &Vec<f64>| vec![2.0 * (p[0] - 3.0), 2.0 * (p[1] + 1.0)];
+        let min = descend(vec![0.0, 0.0], 0.1, 200, grad);
+        assert!((min[0] - 3.0).abs() < 1e-6);
+        assert!((min[1] + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn descend_scalar() {
+        let min = descend(10.0, 0.25, 100, |x: &f64| 2.0 * x);
+        assert!(min.abs() < 1e-9);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn anneal_finds_minimum_of_toy_cost() {
+        // xorshift64, shared by the neighbor move and the acceptance draws.
+        let seed = std::cell::Cell::new(0x2545_f491_4f6c_dd1du64);
+        let next = || {
+            let mut x = seed.get();
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            seed.set(x);
+            x
+        };
+        let cost = |x: &i64| ((x - 7) * (x - 7)) as f64;
+        let neighbor = |x: &i64| if next() % 2 == 0 { x + 1 } else { x - 1 };
+        let uniform = || (next() >> 11) as f64 / (1u64 << 53) as f64;
+        let cooling = |round: usize| 10.0 * 0.95f64.powi(round as i32);
+        let best = anneal(-20, cost, neighbor, cooling, 2000, uniform);
+        assert_eq!(best, 7);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn anneal_zero_steps_returns_initial() {
+        let best = anneal(3.5, |x: &f64| *x, |x| x - 1.0, |_| 1.0, 0, || 0.0);
+        assert_eq!(best, 3.5);
+    }
+
+    #[test]
+    fn newton_finds_cubic_root() {
+        // x^3 - 2x - 5 has a single real root near 2.0946.
+        let f = |x: f64| x * x * x - 2.0 * x - 5.0;
+        let df = |x: f64| 3.0 * x * x - 2.0;
+        let root = newton(2.0, f, df, 1e-12, 50).unwrap();
+        assert!(f(root).abs() < 1e-9);
+        assert!((root - 2.094_551_481_542_327).abs() < 1e-12);
+    }
+
+    #[test]
+    fn newton_rejects_zero_derivative() {
+        assert_eq!(newton(0.0, |x| x * x + 1.0, |x| 2.0 * x, 1e-9, 50), None);
+    }
+
+    #[test]
+    fn newton_gives_up_after_max_iter() {
+        // x^2 + 1 has no real root, so the iterates never settle.
+        assert_eq!(newton(0.5, |x| x * x + 1.0, |x| 2.0 * x, 1e-12, 20), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn power_iteration_symmetric_matrix() {
+        // Eigenvalues (5 ± √5) / 2; the dominant eigenvector is (1, φ).
+        let matrix = vec![vec![2.0, 1.0], vec![1.0, 3.0]];
+        let v = power_iteration(&matrix, 1000, 1e-12);
+        let phi = (1.0 + 5f64.sqrt()) / 2.0;
+        let norm = (1.0 + phi * phi).sqrt();
+        assert!((v[0] - 1.0 / norm).abs() < 1e-9);
+        assert!((v[1] - phi / norm).abs() < 1e-9);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn power_iteration_zero_matrix_keeps_start() {
+        let v = power_iteration(&[vec![0.0, 0.0], vec![0.0, 0.0]], 10, 1e-9);
+        let unit = 1.0 / 2f64.sqrt();
+        assert_eq!(v, vec![unit, unit]);
+    }
+
+    #[test]
+    fn kmeans_separates_two_blobs() {
+        let points = vec![
+            vec![0.0, 0.0],
+            vec![10.0, 10.0],
+            vec![0.5, 0.2],
+            vec![9.5, 10.4],
+            vec![0.1, 0.7],
+            vec![10.2, 9.8],
+        ];
+        let (centroids, assignments) = kmeans(&points, 2, 100);
+        assert_eq!(assignments, vec![0, 1, 0, 1, 0, 1]);
+        assert!((centroids[0][0] - 0.2).abs() < 1e-9);
+        assert!((centroids[1][1] - 10.066_666_666_666_666).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "clusters")]
+    fn kmeans_rejects_too_many_clusters() {
+        kmeans(&[vec![1.0]], 2, 10);
+    }
+
+    #[test]
+    fn fill_table_computes_edit_distance() {
+        let (a, b) = (b"kitten", b"sitting");
+        let table = fill_table(a.len() + 1, b.len() + 1, |i, j, t| match (i, j) {
+            (0, j) => j as u64,
+            (i, 0) => i as u64,
+            _ => {
+                let substitute = t[i - 1][j - 1] + u64::from(a[i - 1] != b[j - 1]);
+                substitute.min(t[i - 1][j] + 1).min(t[i][j - 1] + 1)
+            }
+        });
+        assert_eq!(table[a.len()][b.len()], 3);
+    }
+
+    #[test]
+    fn fill_table_visits_cells_in_order() {
+        let mut next = 0;
+        let table = fill_table(2, 3, |_, _, _| {
+            next += 1;
+            next
+        });
+        assert_eq!(table, vec![vec![1, 2, 3], vec![4, 5, 6]]);
+    }
+
+    #[test]
+    fn linear_recurrence_fibonacci() {
+        assert_eq!(linear_recurrence(&[1, 1], &[0, 1], 10), 55);
+        assert_eq!(linear_recurrence(&[1, 1], &[0, 1], 1), 1);
+    }
+
+    #[test]
+    fn linear_recurrence_coefficient_order() {
+        // a[n] = a[n - 2], so the seeds alternate.
+        assert_eq!(linear_recurrence(&[0, 1], &[3, 7], 4), 3);
+        assert_eq!(linear_recurrence(&[0, 1], &[3, 7], 5), 7);
+    }
+
+    #[test]
+    fn linear_recurrence_wraps_on_overflow() {
+        // fib(93) exceeds i64::MAX; the wrapped value is fib(91) + fib(92).
+        let expected = 4_660_046_610_375_530_309i64.wrapping_add(7_540_113_804_746_346_429);
+        assert_eq!(linear_recurrence(&[1, 1], &[0, 1], 93), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "one seed per coefficient")]
+    fn linear_recurrence_rejects_mismatched_seeds() {
+        linear_recurrence(&[1, 1], &[1], 3);
+    }
+}
@@ -0,0 +1,106 @@
+//! Frame- and tick-driven loops built on [`recur`].
+
+use core::ops::ControlFlow;
+
+use super::recur;
+
+/// Calls `f` with the state and the frame index once per frame, stopping
+/// when `f` returns `false` or after `max_frames` frames.
+///
+/// Returns the state as left by the last frame.
+pub fn run_frames<S, F: FnMut(&mut S, usize) -> bool>(initial: S, max_frames: usize, f: F) -> S {
+    recur((initial, 0, f), |(mut state, frame, mut f)| {
+        if frame >= max_frames || !f(&mut state, frame) {
+            ControlFlow::Break(state)
+        } else {
+            ControlFlow::Continue((state, frame + 1, f))
+        }
+    })
+}
+
+/// Advances the state with `f` in steps of `dt` until `total` simulated
+/// time has elapsed, returning the final state.
+///
+/// `f` receives the length of each step. The last step is shortened to land
+/// exactly on `total`; leftovers smaller than a billionth of `dt` (from
+/// float rounding) are dropped rather than run as a sliver of a step.
+///
+/// # Panics
+///
+/// Panics if `dt` is not positive or `total` is not finite, since either
+/// would keep the loop from ending.
+pub fn fixed_timestep<S, F: FnMut(&mut S, f64)>(initial: S, dt: f64, total: f64, f: F) -> S {
+    assert!(dt > 0.0, "fixed_timestep requires a positive dt");
+    assert!(total.is_finite(), "fixed_timestep requires a finite total");
+    let epsilon = dt * 1e-9;
+    recur((initial, 0.0, f), |(mut state, elapsed, mut f)| {
+        let remaining = total - elapsed;
+        if remaining <= epsilon {
+            return ControlFlow::Break(state);
+        }
+        let step = remaining.min(dt);
+        f(&mut state, step);
+        ControlFlow::Continue((state, elapsed + step, f))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_frames_hits_max() {
+        let frames = run_frames(Vec::new(), 5, |seen, frame| {
+            seen.push(frame);
+            true
+        });
+        assert_eq!(frames, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn run_frames_stops_on_false() {
+        let total = run_frames(0, 100, |sum, frame| {
+            *sum += frame;
+            frame < 3
+        });
+        assert_eq!(total, 6);
+    }
+
+    #[test]
+    fn fixed_timestep_constant_velocity() {
+        let velocity = 3.0;
+        let mut steps = Vec::new();
+        let position = fixed_timestep(0.0, 0.4, 1.0, |x, dt| {
+            steps.push(dt);
+            *x += velocity * dt;
+        });
+        assert!((position - 3.0).abs() < 1e-12);
+        assert_eq!(steps.len(), 3);
+        assert!((steps[2] - 0.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn fixed_timestep_exact_multiple_has_no_sliver() {
+        let mut calls = 0;
+        fixed_timestep((), 0.1, 1.0, |_, _| calls += 1);
+        assert_eq!(calls, 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "positive dt")]
+    fn fixed_timestep_rejects_zero_dt() {
+        fixed_timestep((), 0.0, 1.0, |_, _| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "finite total")]
+    fn fixed_timestep_rejects_nan_total() {
+        fixed_timestep((), 0.1, f64::NAN, |_, _| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "finite total")]
+    fn fixed_timestep_rejects_infinite_total() {
+        fixed_timestep((), 0.1, f64::INFINITY, |_, _| {});
+    }
+}
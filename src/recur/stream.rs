@@ -0,0 +1,173 @@
+//! Loops that move values between producers and consumers.
+
+use core::ops::ControlFlow;
+#[cfg(feature = "std")]
+use std::sync::mpsc::{SendError, SyncSender};
+
+use alloc::vec::Vec;
+
+use super::recur;
+
+/// Pulls values from `produce` and sends them into `sink` until `produce`
+/// returns `None`.
+///
+/// A full channel blocks the loop until the receiver catches up. If the
+/// receiver hangs up, the unsent value is returned in the error.
+#[cfg(feature = "std")]
+pub fn pump<T, F: FnMut() -> Option<T>>(
+    produce: F,
+    sink: &SyncSender<T>,
+) -> Result<(), SendError<T>> {
+    recur(produce, |mut produce| match produce() {
+        None => ControlFlow::Break(Ok(())),
+        Some(value) => match sink.send(value) {
+            Ok(()) => ControlFlow::Continue(produce),
+            Err(err) => ControlFlow::Break(Err(err)),
+        },
+    })
+}
+
+/// Folds `events` into the state with `f`, stopping early if `f` breaks.
+///
+/// Returns the state from the break, or the state after the last event.
+pub fn event_loop<S, E, F>(initial: S, events: impl IntoIterator<Item = E>, f: F) -> S
+where
+    F: FnMut(S, E) -> ControlFlow<S, S>,
+{
+    recur(
+        (initial, events.into_iter(), f),
+        |(state, mut events, mut f)| match events.next() {
+            None => ControlFlow::Break(state),
+            Some(event) => match f(state, event) {
+                ControlFlow::Continue(state) => ControlFlow::Continue((state, events, f)),
+                ControlFlow::Break(state) => ControlFlow::Break(state),
+            },
+        },
+    )
+}
+
+/// Repeatedly calls `f` on the unconsumed input, collecting the token it
+/// returns and skipping ahead by the returned number of bytes.
+///
+/// Scanning stops when the input is exhausted, when `f` returns `None`, or
+/// after a token that consumed zero bytes, since `f` would see the same
+/// input again.
+///
+/// # Panics
+///
+/// Panics if an advance goes past the end of the input or does not land on
+/// a `char` boundary.
+pub fn scan<T, F>(input: &str, f: F) -> Vec<T>
+where
+    F: FnMut(&str) -> Option<(T, usize)>,
+{
+    recur((input, Vec::new(), f), |(rest, mut tokens, mut f)| {
+        if rest.is_empty() {
+            return ControlFlow::Break(tokens);
+        }
+        match f(rest) {
+            None => ControlFlow::Break(tokens),
+            Some((token, advance)) => {
+                tokens.push(token);
+                if advance == 0 {
+                    ControlFlow::Break(tokens)
+                } else {
+                    ControlFlow::Continue((&rest[advance..], tokens, f))
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn pump_through_bounded_channel() {
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+        let producer = std::thread::spawn(move || {
+            let mut items = 1..=5;
+            pump(|| items.next(), &tx)
+        });
+        let received: Vec<i32> = rx.iter().collect();
+        assert_eq!(producer.join().unwrap(), Ok(()));
+        assert_eq!(received, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn pump_stops_when_receiver_hangs_up() {
+        let (tx, rx) = std::sync::mpsc::sync_channel(1);
+        drop(rx);
+        let mut items = 1..=5;
+        assert_eq!(pump(|| items.next(), &tx), Err(SendError(1)));
+    }
+
+    #[derive(Debug)]
+    enum Event {
+        Add(i32),
+        Halt,
+    }
+
+    #[test]
+    fn event_loop_stops_on_halt() {
+        let events = vec![Event::Add(2), Event::Add(3), Event::Halt, Event::Add(100)];
+        let total = event_loop(0, events, |sum, event| match event {
+            Event::Add(n) => ControlFlow::Continue(sum + n),
+            Event::Halt => ControlFlow::Break(sum),
+        });
+        assert_eq!(total, 5);
+    }
+
+    #[test]
+    fn event_loop_exhausts_events() {
+        let events = vec![Event::Add(2), Event::Add(3)];
+        let total = event_loop(1, events, |sum, event| match event {
+            Event::Add(n) => ControlFlow::Continue(sum * n),
+            Event::Halt => ControlFlow::Break(sum),
+        });
+        assert_eq!(total, 6);
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum Token {
+        Number(u32),
+        Word(String),
+    }
+
+    fn next_token(rest: &str) -> Option<(Token, usize)> {
+        let first = rest.chars().next()?;
+        let run = |pred: fn(char) -> bool| rest.find(|c| !pred(c)).unwrap_or(rest.len());
+        if first.is_ascii_digit() {
+            let len = run(|c| c.is_ascii_digit());
+            Some((Token::Number(rest[..len].parse().ok()?), len))
+        } else if first.is_ascii_alphabetic() {
+            let len = run(|c| c.is_ascii_alphabetic());
+            Some((Token::Word(rest[..len].to_string()), len))
+        } else {
+            None
+        }
+    }
+
+    #[test]
+    fn scan_tokenizes_digits_and_letters() {
+        let tokens = scan("12ab3c", next_token);
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Number(12),
+                Token::Word("ab".to_string()),
+                Token::Number(3),
+                Token::Word("c".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_stops_on_unrecognized_input() {
+        let tokens = scan("7x!9", next_token);
+        assert_eq!(tokens, vec![Token::Number(7), Token::Word("x".to_string())]);
+    }
+}
@@ -0,0 +1,118 @@
+//! Loops paced by wall-clock time, built on [`recur`].
+
+use core::ops::ControlFlow;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::recur;
+
+/// Calls `f` until it succeeds or `max_attempts` attempts have failed,
+/// sleeping between attempts.
+///
+/// The first retry waits `base_delay` and each later one doubles the
+/// previous wait, with no jitter: `base_delay`, `2 * base_delay`,
+/// `4 * base_delay`, and so on. At least one attempt is always made, and the
+/// error from the last attempt is returned if none succeed.
+pub fn retry_backoff<T, E, F>(max_attempts: usize, base_delay: Duration, f: F) -> Result<T, E>
+where
+    F: FnMut() -> Result<T, E>,
+{
+    recur((1, base_delay, f), |(attempt, delay, mut f)| match f() {
+        Ok(value) => ControlFlow::Break(Ok(value)),
+        Err(err) if attempt >= max_attempts => ControlFlow::Break(Err(err)),
+        Err(_) => {
+            thread::sleep(delay);
+            ControlFlow::Continue((attempt + 1, delay.saturating_mul(2), f))
+        }
+    })
+}
+
+/// Calls `f` on the state until it breaks, running at most `per_second`
+/// iterations per second, and returns the state.
+///
+/// Iterations are scheduled at fixed intervals from the start, sleeping
+/// only when `f` finishes ahead of schedule, so a slow iteration is not
+/// followed by an extra wait.
+///
+/// # Panics
+///
+/// Panics if `per_second` is not positive and finite, or so small that the
+/// interval between iterations does not fit in a [`Duration`].
+pub fn run_rate_limited<S, F>(initial: S, per_second: f64, f: F) -> S
+where
+    F: FnMut(&mut S) -> ControlFlow<()>,
+{
+    assert!(
+        per_second > 0.0 && per_second.is_finite(),
+        "run_rate_limited requires a positive, finite rate"
+    );
+    let interval = Duration::try_from_secs_f64(1.0 / per_second)
+        .expect("run_rate_limited requires an interval that fits in a Duration");
+    recur((initial, Instant::now(), f), |(mut state, slot, mut f)| {
+        if f(&mut state).is_break() {
+            return ControlFlow::Break(state);
+        }
+        let next_slot = slot + interval;
+        if let Some(wait) = next_slot.checked_duration_since(Instant::now()) {
+            thread::sleep(wait);
+        }
+        ControlFlow::Continue((state, next_slot, f))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_backoff_succeeds_after_failures() {
+        let mut calls = 0;
+        let result = retry_backoff(5, Duration::from_micros(10), || {
+            calls += 1;
+            if calls < 3 {
+                Err("flaky")
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result, Ok(3));
+    }
+
+    #[test]
+    fn retry_backoff_gives_up() {
+        let mut calls = 0;
+        let result: Result<(), _> = retry_backoff(3, Duration::from_micros(10), || {
+            calls += 1;
+            Err(calls)
+        });
+        assert_eq!(result, Err(3));
+    }
+
+    #[test]
+    fn run_rate_limited_runs_expected_iterations() {
+        let start = Instant::now();
+        let count = run_rate_limited(0, 1000.0, |n| {
+            *n += 1;
+            if *n == 5 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(count, 5);
+        // Four waits of a millisecond each separate the five iterations.
+        assert!(start.elapsed() >= Duration::from_millis(4));
+    }
+
+    #[test]
+    #[should_panic(expected = "positive, finite rate")]
+    fn run_rate_limited_rejects_zero_rate() {
+        run_rate_limited((), 0.0, |_| ControlFlow::Break(()));
+    }
+
+    #[test]
+    #[should_panic(expected = "fits in a Duration")]
+    fn run_rate_limited_rejects_tiny_rate() {
+        run_rate_limited((), 1e-20, |_| ControlFlow::Break(()));
+    }
+}